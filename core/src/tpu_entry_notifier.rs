@@ -1,6 +1,5 @@
 use {
     crossbeam_channel::{Receiver, RecvTimeoutError, Sender},
-    solana_entry::entry::EntrySummary,
     solana_ledger::entry_notifier_service::{EntryNotification, EntryNotifierSender},
     solana_poh::poh_recorder::WorkingBankEntry,
     std::{
@@ -69,11 +68,7 @@ impl TpuEntryNotifier {
             *current_index
         };
 
-        let entry_summary = EntrySummary {
-            num_hashes: entry.num_hashes,
-            hash: entry.hash,
-            num_transactions: entry.transactions.len() as u64,
-        };
+        let entry_summary = entry.summary();
         if let Err(err) = entry_notification_sender.send(EntryNotification {
             slot,
             index,
@@ -0,0 +1,34 @@
+#![feature(test)]
+extern crate test;
+use {
+    solana_entry::entry::{self, api, EntrySlice},
+    solana_sdk::hash::Hash,
+    test::Bencher,
+};
+
+// Compares the generic and AVX2 PoH verify paths on the short slices near
+// `MIN_AVX2_VERIFY_ENTRIES`. Run with TEST_PERF_LIBS=1 to load the SIMD lib.
+const NUM_ENTRIES: u64 = 32;
+const HASHES_PER_TICK: u64 = 12_500;
+
+#[bench]
+fn bench_poh_verify_generic_short_slice(bencher: &mut Bencher) {
+    let zero = Hash::default();
+    let entries = entry::create_ticks(NUM_ENTRIES, HASHES_PER_TICK, zero);
+    bencher.iter(|| {
+        assert!(entries.verify_cpu_generic(&zero).finish_verify());
+    })
+}
+
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+#[bench]
+fn bench_poh_verify_avx2_short_slice(bencher: &mut Bencher) {
+    if api().is_none() || !is_x86_feature_detected!("avx2") {
+        return;
+    }
+    let zero = Hash::default();
+    let entries = entry::create_ticks(NUM_ENTRIES, HASHES_PER_TICK, zero);
+    bencher.iter(|| {
+        assert!(entries.verify_cpu_x86_simd(&zero, 8).finish_verify());
+    })
+}
@@ -53,6 +53,8 @@ fn bench_gpusigverify(bencher: &mut Bencher) {
         let res = entry::start_verify_transactions(
             entries.clone(),
             false,
+            false,
+            false,
             recycler.clone(),
             Arc::new(verify_transaction),
         );
@@ -4,10 +4,14 @@
 //! represents an approximate amount of time since the last Entry was created.
 use {
     crate::poh::Poh,
-    crossbeam_channel::{Receiver, Sender},
+    bv::BitVec,
+    crossbeam_channel::{unbounded, Receiver, Sender},
     dlopen2::symbor::{Container, SymBorApi, Symbol},
     lazy_static::lazy_static,
     log::*,
+    lru::LruCache,
+    memmap2::Mmap,
+    once_cell::sync::OnceCell,
     rand::{thread_rng, Rng},
     rayon::{prelude::*, ThreadPool},
     serde::{Deserialize, Serialize},
@@ -23,21 +27,37 @@ use {
     },
     solana_rayon_threadlimit::get_max_thread_count,
     solana_sdk::{
-        hash::Hash,
+        borsh0_10::try_from_slice_unchecked,
+        compute_budget::{self, ComputeBudgetInstruction},
+        ed25519_program,
+        feature_set::{self, FeatureSet},
+        hash::{hashv, Hash, Hasher, HASH_BYTES},
         packet::Meta,
-        timing,
+        precompiles::{verify_if_precompile, PrecompileError},
+        pubkey::Pubkey,
+        secp256k1_program,
+        timing::{self, AtomicInterval},
         transaction::{
             Result, SanitizedTransaction, Transaction, TransactionError,
             TransactionVerificationMode, VersionedTransaction,
         },
+        vote,
     },
     std::{
-        cmp,
+        cell::{Cell, RefCell},
+        collections::{HashMap, HashSet},
         ffi::OsStr,
+        fs::File,
+        io,
         iter::repeat_with,
-        sync::{Arc, Mutex, Once},
+        ops::Range,
+        path::Path,
+        sync::{
+            atomic::{AtomicBool, AtomicU64, Ordering},
+            Arc, Mutex, Once,
+        },
         thread::{self, JoinHandle},
-        time::Instant,
+        time::{Duration, Instant},
     },
 };
 
@@ -49,8 +69,93 @@ lazy_static! {
         .thread_name(|i| format!("solEntry{i:02}"))
         .build()
         .unwrap();
+    /// Precompile instructions are parsed with the stricter rules of these features, which
+    /// every cluster has activated
+    static ref PRECOMPILE_FEATURE_SET: FeatureSet = {
+        let mut features = FeatureSet::default();
+        features.activate(&feature_set::libsecp256k1_fail_on_bad_count::id(), 0);
+        features.activate(&feature_set::libsecp256k1_fail_on_bad_count2::id(), 0);
+        features
+    };
+    /// Transactions per entry of every verified slice, aggregated between datapoints
+    static ref TXS_PER_ENTRY_REPORT: TxsPerEntryReport = TxsPerEntryReport::default();
+    /// Pools of `EntrySlice::verify_with_max_concurrency`, by thread count
+    static ref CAPPED_THREAD_POOLS: Mutex<HashMap<usize, Arc<ThreadPool>>> = Mutex::default();
+}
+
+/// Entries passed to `EntrySlice::start_verify` or `EntrySlice::verify_into` since
+/// process start
+static TOTAL_ENTRIES_VERIFIED: AtomicU64 = AtomicU64::new(0);
+
+/// Total number of entries passed to `EntrySlice::start_verify` or
+/// `EntrySlice::verify_into` since process start,
+/// whether or not they verified. Monotonic, so scrapers can derive long-run throughput.
+pub fn total_entries_verified() -> u64 {
+    TOTAL_ENTRIES_VERIFIED.load(Ordering::Relaxed)
+}
+
+/// Returns the number of threads in the pool used for entry verification
+pub fn verify_thread_count() -> usize {
+    PAR_THREAD_POOL.current_num_threads()
+}
+
+thread_local! {
+    /// Hash and num_hashes buffers reused by `verify_cpu_x86_simd` calls on this thread
+    static SIMD_VERIFY_SCRATCH: RefCell<(Vec<u8>, Vec<u64>)> = RefCell::default();
+    /// Set on the threads of `CAPPED_THREAD_POOLS`
+    static IN_CAPPED_THREAD_POOL: Cell<bool> = Cell::new(false);
+}
+
+/// Runs `op` on `PAR_THREAD_POOL`, or on the current pool if called from one of
+/// `CAPPED_THREAD_POOLS`, so that capped verification stays within its thread count
+fn install_verify_pool<OP, R>(op: OP) -> R
+where
+    OP: FnOnce() -> R + Send,
+    R: Send,
+{
+    if IN_CAPPED_THREAD_POOL.with(Cell::get) {
+        op()
+    } else {
+        PAR_THREAD_POOL.install(op)
+    }
+}
+
+/// Returns the cached pool of `max_threads` threads, building it on first use
+fn capped_thread_pool(max_threads: usize) -> Arc<ThreadPool> {
+    CAPPED_THREAD_POOLS
+        .lock()
+        .unwrap()
+        .entry(max_threads)
+        .or_insert_with(|| {
+            let pool = rayon::ThreadPoolBuilder::new()
+                .num_threads(max_threads)
+                .thread_name(|i| format!("solEntryCap{i:02}"))
+                .start_handler(|_| IN_CAPPED_THREAD_POOL.with(|in_pool| in_pool.set(true)))
+                .build()
+                .unwrap();
+            Arc::new(pool)
+        })
+        .clone()
 }
 
+/// Most entries' worth of capacity `SIMD_VERIFY_SCRATCH` keeps between calls, so a single
+/// unusually large slice doesn't pin its buffers to the thread indefinitely
+const MAX_RETAINED_SIMD_SCRATCH_ENTRIES: usize = 1 << 16;
+
+/// Returns the buffers taken from `SIMD_VERIFY_SCRATCH`, shrinking them first if they grew
+/// past `MAX_RETAINED_SIMD_SCRATCH_ENTRIES`
+fn retain_simd_verify_scratch(mut hashes_bytes: Vec<u8>, mut num_hashes: Vec<u64>) {
+    hashes_bytes.clear();
+    hashes_bytes.shrink_to(HASH_BYTES * MAX_RETAINED_SIMD_SCRATCH_ENTRIES);
+    num_hashes.clear();
+    num_hashes.shrink_to(MAX_RETAINED_SIMD_SCRATCH_ENTRIES);
+    SIMD_VERIFY_SCRATCH.with(|scratch| scratch.replace((hashes_bytes, num_hashes)));
+}
+
+/// Log target for this module's messages, so verification noise can be filtered on its own,
+/// e.g. `RUST_LOG=solana_entry::verify=off`
+pub const LOG_TARGET: &str = "solana_entry::verify";
+
 pub type EntrySender = Sender<Vec<Entry>>;
 pub type EntryReceiver = Receiver<Vec<Entry>>;
 
@@ -63,7 +168,7 @@ pub fn init_poh() {
 fn init(name: &OsStr) {
     static INIT_HOOK: Once = Once::new();
 
-    info!("Loading {:?}", name);
+    info!(target: LOG_TARGET, "Loading {:?}", name);
     unsafe {
         INIT_HOOK.call_once(|| {
             let path;
@@ -96,6 +201,60 @@ pub fn api() -> Option<&'static Container<Api<'static>>> {
     unsafe { API.as_ref() }
 }
 
+/// Returns the perf libs api, but only if a GPU device was found when it was first probed.
+/// The lib may load fine on machines without a CUDA device, in which case callers should
+/// fall back to CPU verification instead of failing inside the GPU thread.
+fn gpu_api() -> Option<&'static Container<perf_libs::Api<'static>>> {
+    static GPU_AVAILABLE: OnceCell<bool> = OnceCell::new();
+    select_gpu_api(perf_libs::api(), &GPU_AVAILABLE, |api| unsafe {
+        (api.ed25519_init)()
+    })
+}
+
+fn select_gpu_api<T>(
+    api: Option<T>,
+    gpu_available: &OnceCell<bool>,
+    probe: impl FnOnce(&T) -> bool,
+) -> Option<T> {
+    let api = api?;
+    let available = *gpu_available.get_or_init(|| {
+        let available = probe(&api);
+        if !available {
+            warn!(
+                target: LOG_TARGET,
+                "perf libs loaded but no GPU device found, verifying entries on the CPU"
+            );
+        }
+        available
+    });
+    available.then_some(api)
+}
+
+/// Symbols `Api` binds from the PoH SIMD library
+const POH_LIB_SYMBOLS: &[&str] = &[
+    "poh_verify_many_simd_avx512skx",
+    "poh_verify_many_simd_avx2",
+];
+
+/// Loads the library at `path` and reports, one line per expected symbol, whether it is
+/// present. `Api` only loads if every symbol is, so this shows which one is missing.
+pub fn diagnose_poh_lib(path: &Path) -> Vec<String> {
+    let lib = match dlopen2::raw::Library::open(path) {
+        Ok(lib) => lib,
+        Err(err) => return vec![format!("unable to load {path:?}: {err}")],
+    };
+    POH_LIB_SYMBOLS
+        .iter()
+        .map(|name| {
+            let status = match unsafe { lib.symbol::<*const u8>(name) } {
+                Ok(_) => "present",
+                Err(_) => "absent",
+            };
+            format!("{name}: {status}")
+        })
+        .collect()
+}
+
 #[derive(SymBorApi)]
 pub struct Api<'a> {
     pub poh_verify_many_simd_avx512skx:
@@ -145,10 +304,13 @@ pub struct Entry {
     pub transactions: Vec<VersionedTransaction>,
 }
 
+/// Compact view of an `Entry` without its transactions
+#[derive(Serialize, Deserialize, Debug, Default, PartialEq, Eq, Clone, Copy)]
 pub struct EntrySummary {
     pub num_hashes: u64,
     pub hash: Hash,
     pub num_transactions: u64,
+    pub is_tick: bool,
 }
 
 impl From<&Entry> for EntrySummary {
@@ -157,6 +319,7 @@ impl From<&Entry> for EntrySummary {
             num_hashes: entry.num_hashes,
             hash: entry.hash,
             num_transactions: entry.transactions.len() as u64,
+            is_tick: entry.is_tick(),
         }
     }
 }
@@ -197,6 +360,16 @@ impl Entry {
         entry
     }
 
+    /// Replaces the transactions and recomputes `hash` from `prev_hash`. Like `Entry::new`,
+    /// `num_hashes` is bumped to 1 if transactions are added to a zero-hash entry.
+    pub fn set_transactions(&mut self, prev_hash: &Hash, transactions: Vec<Transaction>) {
+        if self.num_hashes == 0 && !transactions.is_empty() {
+            self.num_hashes = 1;
+        }
+        self.transactions = transactions.into_iter().map(Into::into).collect();
+        self.hash = next_hash(prev_hash, self.num_hashes, &self.transactions);
+    }
+
     #[cfg(test)]
     pub fn new_tick(num_hashes: u64, hash: &Hash) -> Self {
         Entry {
@@ -209,9 +382,10 @@ impl Entry {
     /// Verifies self.hash is the result of hashing a `start_hash` `self.num_hashes` times.
     /// If the transaction is not a Tick, then hash that as well.
     pub fn verify(&self, start_hash: &Hash) -> bool {
-        let ref_hash = next_hash(start_hash, self.num_hashes, &self.transactions);
-        if self.hash != ref_hash {
+        let ref_hash = self.expected_hash(start_hash);
+        if !hashes_equal(&self.hash, &ref_hash) {
             warn!(
+                target: LOG_TARGET,
                 "next_hash is invalid expected: {:?} actual: {:?}",
                 self.hash, ref_hash
             );
@@ -223,9 +397,35 @@ impl Entry {
     pub fn is_tick(&self) -> bool {
         self.transactions.is_empty()
     }
+
+    /// The hash this entry must have to follow `prev_hash`. Every path that recomputes entry
+    /// hashes checks against this, or against `next_hash_with_mixin` when it already has the
+    /// mixin, so they all agree on which entries are valid.
+    fn expected_hash(&self, prev_hash: &Hash) -> Hash {
+        next_hash(prev_hash, self.num_hashes, &self.transactions)
+    }
+
+    pub fn summary(&self) -> EntrySummary {
+        EntrySummary::from(self)
+    }
 }
 
+// Must match the leaf prefix used by `solana_merkle_tree::MerkleTree`
+const MERKLE_LEAF_PREFIX: &[u8] = &[0];
+
+/// Merkle root over the signatures of `transactions`, which is what a transaction entry
+/// mixes into PoH. A transaction without signatures adds no leaves, so distinct unsigned
+/// transactions share a root. Such a transaction never passes sigverify, and giving it a
+/// leaf of its own would change the entry hashes that existing ledgers verify against, so
+/// that would have to be activated by a feature gate on every verify path.
 pub fn hash_transactions(transactions: &[VersionedTransaction]) -> Hash {
+    // Fast path for the common single signature entry: the root of a one-leaf Merkle tree
+    // is just the leaf hash, so skip building the tree.
+    if let [tx] = transactions {
+        if let [signature] = tx.signatures.as_slice() {
+            return hashv(&[MERKLE_LEAF_PREFIX, signature.as_ref()]);
+        }
+    }
     // a hash of a slice of transactions only needs to hash the signatures
     let signatures: Vec<_> = transactions
         .iter()
@@ -248,19 +448,37 @@ pub fn next_hash(
     num_hashes: u64,
     transactions: &[VersionedTransaction],
 ) -> Hash {
-    if num_hashes == 0 && transactions.is_empty() {
+    let mixin = (!transactions.is_empty()).then(|| hash_transactions(transactions));
+    next_hash_with_mixin(start_hash, num_hashes, mixin)
+}
+
+/// Like `next_hash`, but with the transactions already reduced to their mixin. The final
+/// hash records `mixin` if there is one and ticks otherwise; with neither hashes nor a
+/// mixin, start_hash is returned.
+fn next_hash_with_mixin(start_hash: &Hash, num_hashes: u64, mixin: Option<Hash>) -> Hash {
+    if num_hashes == 0 && mixin.is_none() {
         return *start_hash;
     }
 
     let mut poh = Poh::new(*start_hash, None);
     poh.hash(num_hashes.saturating_sub(1));
-    if transactions.is_empty() {
-        poh.tick().unwrap().hash
-    } else {
-        poh.record(hash_transactions(transactions)).unwrap().hash
+    match mixin {
+        Some(mixin) => poh.record(mixin).unwrap().hash,
+        None => poh.tick().unwrap().hash,
     }
 }
 
+/// Whether `tx` has instructions and all of them invoke the vote program. Program ids are
+/// always static account keys, so no lookup tables need to be resolved.
+fn is_vote_transaction(tx: &VersionedTransaction) -> bool {
+    let account_keys = tx.message.static_account_keys();
+    let instructions = tx.message.instructions();
+    !instructions.is_empty()
+        && instructions.iter().all(|ix| {
+            account_keys.get(usize::from(ix.program_id_index)) == Some(&vote::program::id())
+        })
+}
+
 /// Last action required to verify an entry
 enum VerifyAction {
     /// Mixin a hash before computing the last hash for a transaction entry
@@ -271,21 +489,165 @@ enum VerifyAction {
     None,
 }
 
+/// Computes the last action needed to verify each entry, paired with its expected hash
+fn verify_actions(entries: &[Entry]) -> Vec<(VerifyAction, Hash)> {
+    install_verify_pool(|| {
+        entries
+            .into_par_iter()
+            .map(|entry| {
+                let answer = entry.hash;
+                let action = if entry.transactions.is_empty() {
+                    if entry.num_hashes == 0 {
+                        VerifyAction::None
+                    } else {
+                        VerifyAction::Tick
+                    }
+                } else {
+                    VerifyAction::Mixin(hash_transactions(&entry.transactions))
+                };
+                (action, answer)
+            })
+            .collect()
+    })
+}
+
+/// Counts gathered by the pass that stages a slice for verification, so that reporting them
+/// doesn't take another pass over the slice
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct EntryCounts {
+    num_entries: usize,
+    num_ticks: u64,
+    num_txs: usize,
+    min_txs: usize,
+    max_txs: usize,
+}
+
+impl Default for EntryCounts {
+    fn default() -> Self {
+        Self {
+            num_entries: 0,
+            num_ticks: 0,
+            num_txs: 0,
+            min_txs: usize::MAX,
+            max_txs: 0,
+        }
+    }
+}
+
+impl EntryCounts {
+    fn new(entry: &Entry) -> Self {
+        let mut counts = Self::default();
+        counts.add(entry);
+        counts
+    }
+
+    fn add(&mut self, entry: &Entry) {
+        let num_txs = entry.transactions.len();
+        self.num_entries += 1;
+        self.num_ticks += u64::from(entry.is_tick());
+        self.num_txs += num_txs;
+        self.min_txs = self.min_txs.min(num_txs);
+        self.max_txs = self.max_txs.max(num_txs);
+    }
+
+    fn merge(self, other: Self) -> Self {
+        Self {
+            num_entries: self.num_entries + other.num_entries,
+            num_ticks: self.num_ticks + other.num_ticks,
+            num_txs: self.num_txs + other.num_txs,
+            min_txs: self.min_txs.min(other.min_txs),
+            max_txs: self.max_txs.max(other.max_txs),
+        }
+    }
+}
+
+/// How often `TxsPerEntryReport` submits the transactions per entry it has aggregated
+const TXS_PER_ENTRY_REPORT_INTERVAL_MS: u64 = 2_000;
+
+/// Transactions per entry of the slices passed to `EntrySlice::start_verify`, aggregated
+/// between datapoints instead of submitting one per slice
+#[derive(Default)]
+struct TxsPerEntryReport {
+    last_report: AtomicInterval,
+    counts: Mutex<EntryCounts>,
+}
+
+impl TxsPerEntryReport {
+    fn record(&self, counts: EntryCounts) {
+        let counts = {
+            let mut aggregate = self.counts.lock().unwrap();
+            *aggregate = aggregate.merge(counts);
+            if aggregate.num_entries == 0
+                || !self
+                    .last_report
+                    .should_update(TXS_PER_ENTRY_REPORT_INTERVAL_MS)
+            {
+                return;
+            }
+            std::mem::take(&mut *aggregate)
+        };
+        datapoint_info!(
+            "entry_verify-txs_per_entry",
+            ("min", counts.min_txs as i64, i64),
+            ("max", counts.max_txs as i64, i64),
+            (
+                "mean",
+                counts.num_txs as f64 / counts.num_entries as f64,
+                f64
+            ),
+            ("num_entries", counts.num_entries as i64, i64),
+        );
+    }
+}
+
+/// Applies each entry's last action to the hashes computed by the GPU and compares the
+/// result with the expected hash
+fn check_verify_actions(hashes: &[Hash], verifications: Vec<(VerifyAction, Hash)>) -> bool {
+    install_verify_pool(|| {
+        hashes
+            .into_par_iter()
+            .cloned()
+            .zip(verifications)
+            .all(|(hash, (action, expected))| {
+                let actual = match action {
+                    VerifyAction::Mixin(mixin) => next_hash_with_mixin(&hash, 1, Some(mixin)),
+                    VerifyAction::Tick => next_hash_with_mixin(&hash, 1, None),
+                    VerifyAction::None => hash,
+                };
+                hashes_equal(&actual, &expected)
+            })
+    })
+}
+
 pub struct GpuVerificationData {
-    thread_h: Option<JoinHandle<u64>>,
+    thread_h: Option<JoinHandle<(u64, u64)>>,
     hashes: Option<Arc<Mutex<PinnedVec<Hash>>>>,
     verifications: Option<Vec<(VerifyAction, Hash)>>,
 }
 
+/// PoH chains being hashed on a background CPU thread, see `start_verify_replay`. The
+/// thread returns the hashes and the time it spent on them.
+pub struct CpuVerificationData {
+    thread_h: Option<JoinHandle<(Vec<Hash>, u64)>>,
+    verifications: Option<Vec<(VerifyAction, Hash)>>,
+}
+
 pub enum DeviceVerificationData {
     Cpu(),
     Gpu(GpuVerificationData),
+    CpuBackground(CpuVerificationData),
+    /// The verification device failed before producing a result
+    DeviceFailed(),
 }
 
 pub struct EntryVerificationState {
     verification_status: EntryVerificationStatus,
     poh_duration_us: u64,
+    transaction_duration_us: u64,
+    gpu_wait_us: u64,
+    gpu_compute_us: u64,
     device_verification_data: DeviceVerificationData,
+    entry_counts: EntryCounts,
 }
 
 pub struct GpuSigVerificationData {
@@ -334,6 +696,14 @@ impl EntrySigVerificationState {
     }
 }
 
+/// Slices shorter than this are verified on the CPU even if a GPU is available, since the
+/// kernel launch overhead outweighs the work
+pub const DEFAULT_MIN_GPU_VERIFY_ENTRIES: usize = 1024;
+
+/// Slices longer than this are verified on the CPU rather than staged in pinned GPU buffers
+/// sized to the whole slice
+pub const DEFAULT_MAX_GPU_VERIFY_ENTRIES: usize = 1 << 20;
+
 #[derive(Default, Clone)]
 pub struct VerifyRecyclers {
     hash_recycler: Recycler<PinnedVec<Hash>>,
@@ -341,6 +711,138 @@ pub struct VerifyRecyclers {
     packet_recycler: PacketBatchRecycler,
     out_recycler: Recycler<PinnedVec<u8>>,
     tx_offset_recycler: Recycler<sigverify::TxOffset>,
+    min_gpu_verify_entries: Option<usize>,
+    max_gpu_verify_entries: Option<usize>,
+}
+
+impl VerifyRecyclers {
+    /// Overrides `DEFAULT_MIN_GPU_VERIFY_ENTRIES` for `EntrySlice::start_verify`
+    pub fn with_min_gpu_verify_entries(mut self, min_gpu_verify_entries: usize) -> Self {
+        self.min_gpu_verify_entries = Some(min_gpu_verify_entries);
+        self
+    }
+
+    pub fn min_gpu_verify_entries(&self) -> usize {
+        self.min_gpu_verify_entries
+            .unwrap_or(DEFAULT_MIN_GPU_VERIFY_ENTRIES)
+    }
+
+    /// Overrides `DEFAULT_MAX_GPU_VERIFY_ENTRIES` for `EntrySlice::start_verify`
+    pub fn with_max_gpu_verify_entries(mut self, max_gpu_verify_entries: usize) -> Self {
+        self.max_gpu_verify_entries = Some(max_gpu_verify_entries);
+        self
+    }
+
+    pub fn max_gpu_verify_entries(&self) -> usize {
+        self.max_gpu_verify_entries
+            .unwrap_or(DEFAULT_MAX_GPU_VERIFY_ENTRIES)
+    }
+}
+
+/// Returns the GPU API if the PoH of `entries` should be verified on the GPU, i.e. one is
+/// available and the slice length is within `[min_gpu_verify_entries,
+/// max_gpu_verify_entries]`. Shared by `start_verify` and `verify_into` so they dispatch the
+/// same way.
+fn start_verify_gpu_api(
+    entries: &[Entry],
+    min_gpu_verify_entries: usize,
+    max_gpu_verify_entries: usize,
+) -> Option<&'static Container<perf_libs::Api<'static>>> {
+    select_gpu_verify_api(
+        gpu_api(),
+        entries.len(),
+        min_gpu_verify_entries,
+        max_gpu_verify_entries,
+    )
+}
+
+/// Returns `api` if `num_entries` is within `[min_gpu_verify_entries,
+/// max_gpu_verify_entries]`. Generic over the API so tests can pass a stub.
+fn select_gpu_verify_api<T>(
+    api: Option<T>,
+    num_entries: usize,
+    min_gpu_verify_entries: usize,
+    max_gpu_verify_entries: usize,
+) -> Option<T> {
+    let api = api?;
+    if num_entries < min_gpu_verify_entries {
+        return None;
+    }
+    if num_entries > max_gpu_verify_entries {
+        warn!(
+            target: LOG_TARGET,
+            "verifying {} entries on the CPU, over the GPU limit of {}",
+            num_entries,
+            max_gpu_verify_entries
+        );
+        return None;
+    }
+    inc_new_counter_info!("entry_verify-num_entries", num_entries);
+    Some(api)
+}
+
+/// Largest synthetic slice `calibrate_gpu_threshold` times
+const MAX_CALIBRATION_ENTRIES: usize = 1 << 16;
+
+/// Times CPU and GPU verification of synthetic tick slices of doubling length and returns
+/// the first length at which the GPU was faster on this host, for use with
+/// `VerifyRecyclers::with_min_gpu_verify_entries`. Returns `usize::MAX` if there's no GPU, if
+/// either path fails to verify a calibration slice, or if the GPU never wins up to
+/// `MAX_CALIBRATION_ENTRIES` entries.
+pub fn calibrate_gpu_threshold() -> usize {
+    const HASHES_PER_TICK: u64 = 64;
+    if gpu_api().is_none() {
+        return usize::MAX;
+    }
+    let zero = Hash::default();
+    // Force the GPU path at every length
+    let recyclers = VerifyRecyclers::default().with_min_gpu_verify_entries(0);
+    let mut num_entries = 64;
+    while num_entries <= MAX_CALIBRATION_ENTRIES {
+        let entries = create_ticks(num_entries as u64, HASHES_PER_TICK, zero);
+
+        let cpu_start = Instant::now();
+        let cpu_res = entries.verify_cpu(&zero).finish_verify_classified();
+        let cpu_elapsed = cpu_start.elapsed();
+
+        let gpu_start = Instant::now();
+        let gpu_res = entries
+            .start_verify(&zero, recyclers.clone())
+            .finish_verify_classified();
+        let gpu_elapsed = gpu_start.elapsed();
+
+        if cpu_res.is_err() || gpu_res.is_err() {
+            warn!(
+                target: LOG_TARGET,
+                "calibration: {} entries failed to verify, cpu {:?}, gpu {:?}",
+                num_entries,
+                cpu_res,
+                gpu_res
+            );
+            return usize::MAX;
+        }
+        debug!(
+            target: LOG_TARGET,
+            "calibration: {} entries, cpu {:?}, gpu {:?}",
+            num_entries,
+            cpu_elapsed,
+            gpu_elapsed
+        );
+        if gpu_elapsed < cpu_elapsed {
+            return num_entries;
+        }
+        num_entries *= 2;
+    }
+    usize::MAX
+}
+
+/// Why entry verification failed
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub enum VerifyFailureKind {
+    /// The entries themselves are invalid, so retrying cannot succeed
+    Permanent,
+    /// The verification device failed, so the entries may still be valid
+    Infrastructure,
 }
 
 #[derive(PartialEq, Eq, Clone, Copy, Debug)]
@@ -359,10 +861,52 @@ impl EntryVerificationState {
         self.poh_duration_us
     }
 
+    /// Time spent hashing transactions separately from the PoH chain. Only the GPU path
+    /// splits this out; on the CPU paths it is included in `poh_duration_us`.
+    pub fn transaction_duration_us(&self) -> u64 {
+        self.transaction_duration_us
+    }
+
+    /// Time the GPU thread spent waiting for its inputs before launching the PoH kernel.
+    /// Included in `poh_duration_us`; always 0 on the CPU paths.
+    pub fn gpu_wait_us(&self) -> u64 {
+        self.gpu_wait_us
+    }
+
+    /// Time spent in the GPU PoH kernel itself. Included in `poh_duration_us`; always 0 on
+    /// the CPU paths.
+    pub fn gpu_compute_us(&self) -> u64 {
+        self.gpu_compute_us
+    }
+
+    /// Number of ticks in the slice, counted while staging the verification so callers that
+    /// need both don't make another pass with `EntrySlice::tick_count`. Only complete for a
+    /// slice that verifies, since the generic CPU path stops counting at the first invalid
+    /// entry.
+    pub fn tick_count(&self) -> u64 {
+        self.entry_counts.num_ticks
+    }
+
     pub fn finish_verify(&mut self) -> bool {
-        match &mut self.device_verification_data {
+        match self.finish_verify_classified() {
+            Ok(()) => true,
+            Err(VerifyFailureKind::Permanent) => false,
+            Err(VerifyFailureKind::Infrastructure) => panic!("GPU PoH verify failed"),
+        }
+    }
+
+    /// Same as `finish_verify`, but reports a failure of the GPU or background CPU
+    /// verification thread as `VerifyFailureKind::Infrastructure` instead of panicking.
+    pub fn finish_verify_classified(&mut self) -> std::result::Result<(), VerifyFailureKind> {
+        let res = match &mut self.device_verification_data {
             DeviceVerificationData::Gpu(verification_state) => {
-                let gpu_time_us = verification_state.thread_h.take().unwrap().join().unwrap();
+                let Ok((gpu_wait_us, gpu_compute_us)) =
+                    verification_state.thread_h.take().unwrap().join()
+                else {
+                    warn!(target: LOG_TARGET, "GPU PoH verify thread failed");
+                    self.verification_status = EntryVerificationStatus::Failure;
+                    return Err(VerifyFailureKind::Infrastructure);
+                };
 
                 let mut verify_check_time = Measure::start("verify_check");
                 let hashes = verification_state.hashes.take().unwrap();
@@ -370,35 +914,77 @@ impl EntryVerificationState {
                     .expect("unwrap Arc")
                     .into_inner()
                     .expect("into_inner");
-                let res = PAR_THREAD_POOL.install(|| {
-                    hashes
-                        .into_par_iter()
-                        .cloned()
-                        .zip(verification_state.verifications.take().unwrap())
-                        .all(|(hash, (action, expected))| {
-                            let actual = match action {
-                                VerifyAction::Mixin(mixin) => {
-                                    Poh::new(hash, None).record(mixin).unwrap().hash
-                                }
-                                VerifyAction::Tick => Poh::new(hash, None).tick().unwrap().hash,
-                                VerifyAction::None => hash,
-                            };
-                            actual == expected
-                        })
-                });
+                let res = check_verify_actions(
+                    &hashes[..],
+                    verification_state.verifications.take().unwrap(),
+                );
                 verify_check_time.stop();
-                self.poh_duration_us += gpu_time_us + verify_check_time.as_us();
-
-                self.verification_status = if res {
-                    EntryVerificationStatus::Success
-                } else {
-                    EntryVerificationStatus::Failure
+                self.gpu_wait_us = gpu_wait_us;
+                self.gpu_compute_us = gpu_compute_us;
+                self.poh_duration_us += gpu_wait_us + gpu_compute_us + verify_check_time.as_us();
+                res
+            }
+            DeviceVerificationData::CpuBackground(verification_state) => {
+                let Ok((hashes, hash_time_us)) = verification_state.thread_h.take().unwrap().join()
+                else {
+                    warn!(target: LOG_TARGET, "CPU PoH verify thread failed");
+                    self.verification_status = EntryVerificationStatus::Failure;
+                    return Err(VerifyFailureKind::Infrastructure);
                 };
+
+                let mut verify_check_time = Measure::start("verify_check");
+                let res =
+                    check_verify_actions(&hashes, verification_state.verifications.take().unwrap());
+                verify_check_time.stop();
+                self.poh_duration_us += hash_time_us + verify_check_time.as_us();
                 res
             }
             DeviceVerificationData::Cpu() => {
                 self.verification_status == EntryVerificationStatus::Success
             }
+            DeviceVerificationData::DeviceFailed() => {
+                self.verification_status = EntryVerificationStatus::Failure;
+                return Err(VerifyFailureKind::Infrastructure);
+            }
+        };
+
+        if res {
+            self.verification_status = EntryVerificationStatus::Success;
+            Ok(())
+        } else {
+            self.verification_status = EntryVerificationStatus::Failure;
+            Err(VerifyFailureKind::Permanent)
+        }
+    }
+
+    /// Same as `finish_verify`, but stops waiting on the GPU thread once `cancel` is set and
+    /// returns `None`. The GPU thread is detached, its result is discarded, and the state is
+    /// left as failed.
+    pub fn finish_verify_cancellable(&mut self, cancel: &AtomicBool) -> Option<bool> {
+        const CANCEL_POLL_INTERVAL: Duration = Duration::from_millis(1);
+        loop {
+            let finished = match &self.device_verification_data {
+                DeviceVerificationData::Gpu(verification_state) => verification_state
+                    .thread_h
+                    .as_ref()
+                    .map_or(true, JoinHandle::is_finished),
+                DeviceVerificationData::CpuBackground(verification_state) => verification_state
+                    .thread_h
+                    .as_ref()
+                    .map_or(true, JoinHandle::is_finished),
+                DeviceVerificationData::Cpu() | DeviceVerificationData::DeviceFailed() => true,
+            };
+            if finished {
+                return Some(self.finish_verify());
+            }
+            if cancel.load(Ordering::Relaxed) {
+                // The GPU and background CPU threads own everything they work on, so
+                // dropping their handle detaches them safely
+                self.device_verification_data = DeviceVerificationData::Cpu();
+                self.verification_status = EntryVerificationStatus::Failure;
+                return None;
+            }
+            thread::sleep(CANCEL_POLL_INTERVAL);
         }
     }
 }
@@ -427,9 +1013,96 @@ pub fn verify_transactions(
     })
 }
 
+/// Verifies the signatures of transactions the caller has already sanitized, one `Vec` per
+/// entry, without re-sanitizing them like `verify_transactions` does.
+pub fn verify_sanitized_transactions(transactions: &[Vec<SanitizedTransaction>]) -> Result<()> {
+    PAR_THREAD_POOL.install(|| {
+        transactions
+            .par_iter()
+            .flatten()
+            .try_for_each(SanitizedTransaction::verify)
+    })
+}
+
+/// Compute units each instruction of a transaction without a `SetComputeUnitLimit`
+/// instruction gets, as in `solana_program_runtime::compute_budget`
+const DEFAULT_INSTRUCTION_COMPUTE_UNIT_LIMIT: u32 = 200_000;
+/// Most compute units a transaction can request, as in `solana_program_runtime::compute_budget`
+const MAX_COMPUTE_UNIT_LIMIT: u32 = 1_400_000;
+
+/// Compute unit limit `tx` requests: the limit of its `SetComputeUnitLimit` instruction if it
+/// has one, else `DEFAULT_INSTRUCTION_COMPUTE_UNIT_LIMIT` for each of its other instructions,
+/// capped at `MAX_COMPUTE_UNIT_LIMIT` as the runtime does. Compute budget instructions the
+/// runtime would reject as invalid or duplicate are not checked.
+fn requested_compute_units(tx: &VersionedTransaction) -> u64 {
+    let account_keys = tx.message.static_account_keys();
+    let mut num_non_compute_budget_instructions: u32 = 0;
+    let mut compute_unit_limit = None;
+    for instruction in tx.message.instructions() {
+        let is_compute_budget = account_keys
+            .get(usize::from(instruction.program_id_index))
+            .map_or(false, compute_budget::check_id);
+        if !is_compute_budget {
+            num_non_compute_budget_instructions =
+                num_non_compute_budget_instructions.saturating_add(1);
+        } else if let Ok(ComputeBudgetInstruction::SetComputeUnitLimit(units)) =
+            try_from_slice_unchecked(&instruction.data)
+        {
+            compute_unit_limit = Some(units);
+        }
+    }
+    let compute_unit_limit = compute_unit_limit
+        .unwrap_or_else(|| {
+            num_non_compute_budget_instructions
+                .saturating_mul(DEFAULT_INSTRUCTION_COMPUTE_UNIT_LIMIT)
+        })
+        .min(MAX_COMPUTE_UNIT_LIMIT);
+    u64::from(compute_unit_limit)
+}
+
+/// Verifies the secp256k1 and ed25519 precompile instructions of `tx` whose program is
+/// enabled, and returns whether `tx` has any precompile instructions at all, enabled or not
+fn verify_precompile_instructions(
+    tx: &VersionedTransaction,
+    secp256k1_program_enabled: bool,
+    ed25519_program_enabled: bool,
+) -> std::result::Result<bool, PrecompileError> {
+    let account_keys = tx.message.static_account_keys();
+    let instructions = tx.message.instructions();
+    let mut has_precompile = false;
+    for instruction in instructions {
+        let Some(program_id) = account_keys.get(usize::from(instruction.program_id_index)) else {
+            continue;
+        };
+        let enabled = if secp256k1_program::check_id(program_id) {
+            secp256k1_program_enabled
+        } else if ed25519_program::check_id(program_id) {
+            ed25519_program_enabled
+        } else {
+            continue;
+        };
+        has_precompile = true;
+        if enabled {
+            verify_if_precompile(
+                program_id,
+                instruction,
+                instructions,
+                &PRECOMPILE_FEATURE_SET,
+            )?;
+        }
+    }
+    Ok(has_precompile)
+}
+
+/// Unless `skip_verification` is set, the secp256k1 and ed25519 precompile instructions of
+/// each transaction are verified when their program is enabled, in addition to whatever
+/// `verify` checks. A failed precompile is reported as
+/// `TransactionError::InvalidAccountIndex`, like `SanitizedTransaction::verify_precompiles`.
 pub fn start_verify_transactions(
     entries: Vec<Entry>,
     skip_verification: bool,
+    secp256k1_program_enabled: bool,
+    ed25519_program_enabled: bool,
     verify_recyclers: VerifyRecyclers,
     verify: Arc<
         dyn Fn(VersionedTransaction, TransactionVerificationMode) -> Result<SanitizedTransaction>
@@ -437,6 +1110,28 @@ pub fn start_verify_transactions(
             + Sync,
     >,
 ) -> Result<EntrySigVerificationState> {
+    let verify: Arc<
+        dyn Fn(VersionedTransaction, TransactionVerificationMode) -> Result<SanitizedTransaction>
+            + Send
+            + Sync,
+    > = if secp256k1_program_enabled || ed25519_program_enabled {
+        Arc::new(
+            move |versioned_tx: VersionedTransaction,
+                  verification_mode: TransactionVerificationMode| {
+                if verification_mode != TransactionVerificationMode::HashOnly {
+                    verify_precompile_instructions(
+                        &versioned_tx,
+                        secp256k1_program_enabled,
+                        ed25519_program_enabled,
+                    )
+                    .map_err(|_| TransactionError::InvalidAccountIndex)?;
+                }
+                verify(versioned_tx, verification_mode)
+            },
+        )
+    } else {
+        verify
+    };
     let api = perf_libs::api();
 
     // Use the CPU if we have too few transactions for GPU signature verification to be worth it.
@@ -465,66 +1160,336 @@ pub fn start_verify_transactions(
     }
 }
 
-fn start_verify_transactions_cpu(
-    entries: Vec<Entry>,
-    skip_verification: bool,
-    verify: Arc<
-        dyn Fn(VersionedTransaction, TransactionVerificationMode) -> Result<SanitizedTransaction>
-            + Send
-            + Sync,
-    >,
-) -> Result<EntrySigVerificationState> {
-    let verify_func = {
-        let mode = if skip_verification {
-            TransactionVerificationMode::HashOnly
-        } else {
-            TransactionVerificationMode::FullVerification
-        };
+/// PoH and transaction verification of the same slice, started together so that the PoH
+/// work on the GPU overlaps the transaction verification on the CPU
+pub struct ReplayVerificationState {
+    poh_verification: EntryVerificationState,
+    transaction_verification: EntrySigVerificationState,
+}
 
-        move |versioned_tx| verify(versioned_tx, mode)
-    };
+impl ReplayVerificationState {
+    pub fn entries(&mut self) -> Option<Vec<EntryType>> {
+        self.transaction_verification.entries()
+    }
 
-    let entries = verify_transactions(entries, Arc::new(verify_func))?;
+    /// Waits for both verifications and returns whether both succeeded
+    pub fn finish_verify(&mut self) -> bool {
+        let transactions_verified = self.transaction_verification.finish_verify();
+        let poh_verified = self.poh_verification.finish_verify();
+        transactions_verified && poh_verified
+    }
 
-    Ok(EntrySigVerificationState {
-        verification_status: EntryVerificationStatus::Success,
-        entries: Some(entries),
-        device_verification_data: DeviceSigVerificationData::Cpu(),
-        gpu_verify_duration_us: 0,
-    })
+    pub fn poh_duration_us(&self) -> u64 {
+        self.poh_verification.poh_duration_us()
+    }
+
+    pub fn gpu_verify_duration(&self) -> u64 {
+        self.transaction_verification.gpu_verify_duration()
+    }
 }
 
-fn start_verify_transactions_gpu(
-    entries: Vec<Entry>,
-    verify_recyclers: VerifyRecyclers,
-    verify: Arc<
-        dyn Fn(VersionedTransaction, TransactionVerificationMode) -> Result<SanitizedTransaction>
-            + Send
-            + Sync,
-    >,
-) -> Result<EntrySigVerificationState> {
-    let verify_func = {
-        move |versioned_tx: VersionedTransaction| -> Result<SanitizedTransaction> {
-            verify(
-                versioned_tx,
-                TransactionVerificationMode::HashAndVerifyPrecompiles,
-            )
-        }
+/// Body of `EntrySlice::start_verify`. Slices that aren't verified on the GPU are verified
+/// on the CPU, synchronously or, if `cpu_in_background`, on a background thread.
+fn start_verify_entries(
+    entries: &[Entry],
+    start_hash: &Hash,
+    recyclers: VerifyRecyclers,
+    cpu_in_background: bool,
+) -> EntryVerificationState {
+    // Nothing to verify, so skip the genesis setup and recycler allocations entirely
+    if entries.is_empty() {
+        return EntryVerificationState {
+            verification_status: EntryVerificationStatus::Success,
+            poh_duration_us: 0,
+            transaction_duration_us: 0,
+            gpu_wait_us: 0,
+            gpu_compute_us: 0,
+            entry_counts: EntryCounts::default(),
+            device_verification_data: DeviceVerificationData::Cpu(),
+        };
+    }
+    TOTAL_ENTRIES_VERIFIED.fetch_add(entries.len() as u64, Ordering::Relaxed);
+    let start = Instant::now();
+    let Some(api) = start_verify_gpu_api(
+        entries,
+        recyclers.min_gpu_verify_entries(),
+        recyclers.max_gpu_verify_entries(),
+    ) else {
+        let state = if cpu_in_background {
+            start_verify_cpu_background(entries, start_hash)
+        } else {
+            entries.verify_cpu(start_hash)
+        };
+        TXS_PER_ENTRY_REPORT.record(state.entry_counts);
+        return state;
     };
 
-    let entries = verify_transactions(entries, Arc::new(verify_func))?;
+    let mut hashes_pinned = recyclers.hash_recycler.allocate("poh_verify_hash");
+    let mut num_hashes_vec = recyclers
+        .tick_count_recycler
+        .allocate("poh_verify_num_hashes");
+    let entry_counts =
+        stage_gpu_poh_verify(entries, start_hash, &mut hashes_pinned, &mut num_hashes_vec);
+    TXS_PER_ENTRY_REPORT.record(entry_counts);
 
-    let entry_txs: Vec<&SanitizedTransaction> = entries
-        .iter()
-        .filter_map(|entry_type| match entry_type {
-            EntryType::Tick(_) => None,
-            EntryType::Transactions(transactions) => Some(transactions),
-        })
-        .flatten()
-        .collect::<Vec<_>>();
+    let length = entries.len();
+    let hashes = Arc::new(Mutex::new(hashes_pinned));
+    let hashes_clone = hashes.clone();
 
-    if entry_txs.is_empty() {
-        return Ok(EntrySigVerificationState {
+    let gpu_verify_thread = thread::Builder::new()
+        .name("solGpuPohVerify".into())
+        .spawn(move || {
+            let gpu_wait = Instant::now();
+            let mut hashes = hashes_clone.lock().unwrap();
+            let gpu_wait_us = timing::duration_as_us(&gpu_wait.elapsed());
+
+            let gpu_compute = Instant::now();
+            let res;
+            unsafe {
+                res = (api.poh_verify_many)(
+                    hashes.as_mut_ptr() as *mut u8,
+                    num_hashes_vec.as_ptr(),
+                    length,
+                    1,
+                );
+            }
+            let gpu_compute_us = timing::duration_as_us(&gpu_compute.elapsed());
+            assert!(res == 0, "GPU PoH verify many failed");
+            inc_new_counter_info!("entry_verify-gpu_wait", gpu_wait_us as usize);
+            inc_new_counter_info!("entry_verify-gpu_compute", gpu_compute_us as usize);
+            (gpu_wait_us, gpu_compute_us)
+        })
+        .unwrap();
+
+    let mut transaction_time = Measure::start("entry_verify_transactions");
+    let verifications = verify_actions(entries);
+    transaction_time.stop();
+    let device_verification_data = DeviceVerificationData::Gpu(GpuVerificationData {
+        thread_h: Some(gpu_verify_thread),
+        verifications: Some(verifications),
+        hashes: Some(hashes),
+    });
+    EntryVerificationState {
+        verification_status: EntryVerificationStatus::Pending,
+        poh_duration_us: timing::duration_as_us(&start.elapsed()),
+        transaction_duration_us: transaction_time.as_us(),
+        gpu_wait_us: 0,
+        gpu_compute_us: 0,
+        entry_counts,
+        device_verification_data,
+    }
+}
+
+/// Fills `hashes_pinned` with the hash each entry's PoH starts from and `num_hashes_pinned`
+/// with the plain hashes before its last one, as `poh_verify_many` takes them, and returns
+/// the counts of `entries`, gathered in the same pass. `entries` must not be empty.
+fn stage_gpu_poh_verify(
+    entries: &[Entry],
+    start_hash: &Hash,
+    hashes_pinned: &mut PinnedVec<Hash>,
+    num_hashes_pinned: &mut PinnedVec<u64>,
+) -> EntryCounts {
+    hashes_pinned.truncate(0);
+    hashes_pinned.reserve_and_pin(entries.len());
+    hashes_pinned.push(*start_hash);
+    for entry in &entries[..entries.len() - 1] {
+        hashes_pinned.push(entry.hash);
+    }
+
+    num_hashes_pinned.truncate(0);
+    num_hashes_pinned.reserve_and_pin(entries.len());
+    let mut entry_counts = EntryCounts::default();
+    for entry in entries {
+        num_hashes_pinned.push(entry.num_hashes.saturating_sub(1));
+        entry_counts.add(entry);
+    }
+    entry_counts
+}
+
+/// Hashes each of `hashes` in place `num_hashes` times, in parallel, using the SIMD PoH
+/// kernels if the host has them
+fn hash_poh_chains(hashes: &mut [Hash], num_hashes: &[u64]) {
+    const MAX_SIMD_LEN: usize = 16;
+    let simd_len = match cpu_verify_path(hashes.len()) {
+        VerifyPath::Avx512 => 16,
+        VerifyPath::Avx2 => 8,
+        VerifyPath::Gpu | VerifyPath::Generic => {
+            install_verify_pool(|| {
+                hashes
+                    .par_iter_mut()
+                    .zip(num_hashes)
+                    .for_each(|(hash, num_hashes)| *hash = next_hash(hash, *num_hashes, &[]));
+            });
+            return;
+        }
+    };
+    install_verify_pool(|| {
+        hashes
+            .par_chunks_mut(simd_len)
+            .zip(num_hashes.par_chunks(simd_len))
+            .for_each(|(hashes, num_hashes)| {
+                // The kernels always hash `simd_len` lanes, so the last chunk is padded
+                let mut lanes = [0u8; MAX_SIMD_LEN * HASH_BYTES];
+                let mut lane_num_hashes = [0u64; MAX_SIMD_LEN];
+                for (lane, hash) in lanes.chunks_mut(HASH_BYTES).zip(hashes.iter()) {
+                    lane.copy_from_slice(hash.as_ref());
+                }
+                lane_num_hashes[..num_hashes.len()].copy_from_slice(num_hashes);
+                let simd_api = api().unwrap();
+                unsafe {
+                    if simd_len == 16 {
+                        (simd_api.poh_verify_many_simd_avx512skx)(
+                            lanes.as_mut_ptr(),
+                            lane_num_hashes.as_ptr(),
+                        );
+                    } else {
+                        (simd_api.poh_verify_many_simd_avx2)(
+                            lanes.as_mut_ptr(),
+                            lane_num_hashes.as_ptr(),
+                        );
+                    }
+                }
+                for (hash, lane) in hashes.iter_mut().zip(lanes.chunks(HASH_BYTES)) {
+                    *hash = Hash::new(lane);
+                }
+            });
+    });
+}
+
+/// Same as `EntrySlice::verify_cpu`, but returns a `Pending` state while the PoH chains are
+/// hashed on a background thread, so that the caller can verify transactions meanwhile.
+/// Transaction hashes are mixed in on the calling thread, like on the GPU path.
+fn start_verify_cpu_background(entries: &[Entry], start_hash: &Hash) -> EntryVerificationState {
+    let start = Instant::now();
+    let mut hashes = Vec::with_capacity(entries.len());
+    let mut num_hashes = Vec::with_capacity(entries.len());
+    let mut entry_counts = EntryCounts::default();
+    let mut prev_hash = *start_hash;
+    for entry in entries {
+        hashes.push(prev_hash);
+        num_hashes.push(entry.num_hashes.saturating_sub(1));
+        entry_counts.add(entry);
+        prev_hash = entry.hash;
+    }
+
+    let cpu_verify_thread = thread::Builder::new()
+        .name("solCpuPohVerify".into())
+        .spawn(move || {
+            let hash_time = Instant::now();
+            hash_poh_chains(&mut hashes, &num_hashes);
+            (hashes, timing::duration_as_us(&hash_time.elapsed()))
+        })
+        .unwrap();
+
+    let mut transaction_time = Measure::start("entry_verify_transactions");
+    let verifications = verify_actions(entries);
+    transaction_time.stop();
+    EntryVerificationState {
+        verification_status: EntryVerificationStatus::Pending,
+        poh_duration_us: timing::duration_as_us(&start.elapsed()),
+        transaction_duration_us: transaction_time.as_us(),
+        gpu_wait_us: 0,
+        gpu_compute_us: 0,
+        entry_counts,
+        device_verification_data: DeviceVerificationData::CpuBackground(CpuVerificationData {
+            thread_h: Some(cpu_verify_thread),
+            verifications: Some(verifications),
+        }),
+    }
+}
+
+/// Starts PoH verification of `entries` from `start_hash`, then transaction verification,
+/// without waiting on the PoH work in between. PoH that doesn't go to the GPU is hashed on a
+/// background CPU thread, so it overlaps the transaction verification either way.
+pub fn start_verify_replay(
+    entries: Vec<Entry>,
+    start_hash: &Hash,
+    skip_verification: bool,
+    secp256k1_program_enabled: bool,
+    ed25519_program_enabled: bool,
+    verify_recyclers: VerifyRecyclers,
+    verify: Arc<
+        dyn Fn(VersionedTransaction, TransactionVerificationMode) -> Result<SanitizedTransaction>
+            + Send
+            + Sync,
+    >,
+) -> Result<ReplayVerificationState> {
+    let poh_verification =
+        start_verify_entries(&entries, start_hash, verify_recyclers.clone(), true);
+    let transaction_verification = start_verify_transactions(
+        entries,
+        skip_verification,
+        secp256k1_program_enabled,
+        ed25519_program_enabled,
+        verify_recyclers,
+        verify,
+    )?;
+    Ok(ReplayVerificationState {
+        poh_verification,
+        transaction_verification,
+    })
+}
+
+fn start_verify_transactions_cpu(
+    entries: Vec<Entry>,
+    skip_verification: bool,
+    verify: Arc<
+        dyn Fn(VersionedTransaction, TransactionVerificationMode) -> Result<SanitizedTransaction>
+            + Send
+            + Sync,
+    >,
+) -> Result<EntrySigVerificationState> {
+    let verify_func = {
+        let mode = if skip_verification {
+            TransactionVerificationMode::HashOnly
+        } else {
+            TransactionVerificationMode::FullVerification
+        };
+
+        move |versioned_tx| verify(versioned_tx, mode)
+    };
+
+    let entries = verify_transactions(entries, Arc::new(verify_func))?;
+
+    Ok(EntrySigVerificationState {
+        verification_status: EntryVerificationStatus::Success,
+        entries: Some(entries),
+        device_verification_data: DeviceSigVerificationData::Cpu(),
+        gpu_verify_duration_us: 0,
+    })
+}
+
+fn start_verify_transactions_gpu(
+    entries: Vec<Entry>,
+    verify_recyclers: VerifyRecyclers,
+    verify: Arc<
+        dyn Fn(VersionedTransaction, TransactionVerificationMode) -> Result<SanitizedTransaction>
+            + Send
+            + Sync,
+    >,
+) -> Result<EntrySigVerificationState> {
+    let verify_func = {
+        move |versioned_tx: VersionedTransaction| -> Result<SanitizedTransaction> {
+            verify(
+                versioned_tx,
+                TransactionVerificationMode::HashAndVerifyPrecompiles,
+            )
+        }
+    };
+
+    let entries = verify_transactions(entries, Arc::new(verify_func))?;
+
+    let entry_txs: Vec<&SanitizedTransaction> = entries
+        .iter()
+        .filter_map(|entry_type| match entry_type {
+            EntryType::Tick(_) => None,
+            EntryType::Transactions(transactions) => Some(transactions),
+        })
+        .flatten()
+        .collect::<Vec<_>>();
+
+    if entry_txs.is_empty() {
+        return Ok(EntrySigVerificationState {
             verification_status: EntryVerificationStatus::Success,
             entries: Some(entries),
             device_verification_data: DeviceSigVerificationData::Cpu(),
@@ -601,35 +1566,612 @@ fn start_verify_transactions_gpu(
     })
 }
 
+/// Verifies the signatures of every transaction in `slices` in one `ed25519_verify` batch,
+/// on the GPU if available, instead of one batch per slice, and returns whether each slice's
+/// signatures all verified. The secp256k1 and ed25519 precompile instructions are verified
+/// as well when `secp256k1_program_enabled` and `ed25519_program_enabled` respectively, and a
+/// slice fails if any of them doesn't. The packet batches and GPU sigverify buffers are
+/// drawn from `verify_recyclers`, as in `start_verify_transactions`, so that repeated calls
+/// reuse the pinned allocations instead of pinning new ones every batch.
+pub fn verify_signatures_batch(
+    slices: &[&[Entry]],
+    secp256k1_program_enabled: bool,
+    ed25519_program_enabled: bool,
+    verify_recyclers: &VerifyRecyclers,
+) -> Vec<bool> {
+    let txs: Vec<(usize, &VersionedTransaction)> = slices
+        .iter()
+        .enumerate()
+        .flat_map(|(i, entries)| {
+            entries
+                .iter()
+                .flat_map(|entry| &entry.transactions)
+                .map(move |tx| (i, tx))
+        })
+        .collect();
+    let mut verified = vec![true; slices.len()];
+    if txs.is_empty() {
+        return verified;
+    }
+
+    let mut packet_batches: Vec<PacketBatch> = PAR_THREAD_POOL.install(|| {
+        txs.par_chunks(PACKETS_PER_BATCH)
+            .map(|chunk| {
+                let packets = chunk
+                    .iter()
+                    .map(|(_, tx)| {
+                        let precompiles_verified = verify_precompile_instructions(
+                            tx,
+                            secp256k1_program_enabled,
+                            ed25519_program_enabled,
+                        )
+                        .is_ok();
+                        // A transaction that doesn't fit in a packet, or whose precompiles
+                        // don't verify, can't verify
+                        match Packet::from_data(None, tx) {
+                            Ok(packet) if precompiles_verified => packet,
+                            _ => {
+                                let mut packet = Packet::default();
+                                packet.meta_mut().set_discard(true);
+                                packet
+                            }
+                        }
+                    })
+                    .collect();
+                PacketBatch::new_with_recycler_data(
+                    &verify_recyclers.packet_recycler,
+                    "entry-sig-verify-batch",
+                    packets,
+                )
+            })
+            .collect()
+    });
+    let num_packets = packet_batches
+        .iter()
+        .flat_map(|batch| batch.iter())
+        .filter(|packet| !packet.meta().discard())
+        .count();
+    sigverify::ed25519_verify(
+        &mut packet_batches,
+        &verify_recyclers.tx_offset_recycler,
+        &verify_recyclers.out_recycler,
+        false,
+        num_packets,
+    );
+
+    let packets = packet_batches.iter().flat_map(|batch| batch.iter());
+    for ((slice_index, _), packet) in txs.iter().zip(packets) {
+        if packet.meta().discard() {
+            verified[*slice_index] = false;
+        }
+    }
+    verified
+}
+
+/// Hash equality used by the verification paths. With the `ct-compare` feature this runs in
+/// constant time, which changes only the timing, never the result.
+#[cfg(feature = "ct-compare")]
+fn hashes_equal(a: &Hash, b: &Hash) -> bool {
+    use subtle::ConstantTimeEq;
+    a.as_ref().ct_eq(b.as_ref()).into()
+}
+
+#[cfg(not(feature = "ct-compare"))]
+fn hashes_equal(a: &Hash, b: &Hash) -> bool {
+    a == b
+}
+
 fn compare_hashes(computed_hash: Hash, ref_entry: &Entry) -> bool {
-    let actual = if !ref_entry.transactions.is_empty() {
-        let tx_hash = hash_transactions(&ref_entry.transactions);
-        let mut poh = Poh::new(computed_hash, None);
-        poh.record(tx_hash).unwrap().hash
-    } else if ref_entry.num_hashes > 0 {
-        let mut poh = Poh::new(computed_hash, None);
-        poh.tick().unwrap().hash
+    // Transactions are always mixed in, so only a tick with no hashes passes the computed hash
+    // through. Like `next_hash`, a transaction entry with no hashes hashes as if it had one.
+    let mixin =
+        (!ref_entry.transactions.is_empty()).then(|| hash_transactions(&ref_entry.transactions));
+    let actual = next_hash_with_mixin(&computed_hash, ref_entry.num_hashes.min(1), mixin);
+    hashes_equal(&actual, &ref_entry.hash)
+}
+
+/// Body of `EntrySlice::verify_cpu_x86_simd_per_chunk`, which also returns the counts of
+/// the slice, gathered while staging it
+fn verify_cpu_x86_simd_chunks(
+    entries: &[Entry],
+    start_hash: &Hash,
+    simd_len: usize,
+) -> (Vec<bool>, EntryCounts) {
+    let genesis = [Entry {
+        num_hashes: 0,
+        hash: *start_hash,
+        transactions: vec![],
+    }];
+
+    let aligned_len = ((entries.len() + simd_len - 1) / simd_len) * simd_len;
+    // Reuse this thread's scratch buffers. They are taken rather than borrowed, so a
+    // nested call made while this thread steals work from the pool just allocates.
+    let (mut hashes_bytes, mut num_hashes) = SIMD_VERIFY_SCRATCH.with(RefCell::take);
+    hashes_bytes.clear();
+    hashes_bytes.resize(HASH_BYTES * aligned_len, 0);
+    genesis
+        .iter()
+        .chain(entries)
+        .enumerate()
+        .for_each(|(i, entry)| {
+            if i < entries.len() {
+                let start = i * HASH_BYTES;
+                let end = start + HASH_BYTES;
+                hashes_bytes[start..end].copy_from_slice(&entry.hash.to_bytes());
+            }
+        });
+    let mut hashes_chunked: Vec<_> = hashes_bytes.chunks_mut(simd_len * HASH_BYTES).collect();
+
+    num_hashes.clear();
+    let mut entry_counts = EntryCounts::default();
+    num_hashes.extend(entries.iter().map(|entry| {
+        entry_counts.add(entry);
+        entry.num_hashes.saturating_sub(1)
+    }));
+    num_hashes.resize(aligned_len, 0);
+    let num_hashes_chunked: Vec<_> = num_hashes.chunks(simd_len).collect();
+
+    let res = install_verify_pool(|| {
+        hashes_chunked
+            .par_iter_mut()
+            .zip(num_hashes_chunked)
+            .enumerate()
+            .map(|(i, (chunk, num_hashes))| {
+                match simd_len {
+                    8 => unsafe {
+                        (api().unwrap().poh_verify_many_simd_avx2)(
+                            chunk.as_mut_ptr(),
+                            num_hashes.as_ptr(),
+                        );
+                    },
+                    16 => unsafe {
+                        (api().unwrap().poh_verify_many_simd_avx512skx)(
+                            chunk.as_mut_ptr(),
+                            num_hashes.as_ptr(),
+                        );
+                    },
+                    _ => {
+                        panic!("unsupported simd len: {simd_len}");
+                    }
+                }
+                let entry_start = i * simd_len;
+                // The last chunk may produce indexes larger than what we have in the reference entries
+                // because it is aligned to simd_len.
+                let entry_end = std::cmp::min(entry_start + simd_len, entries.len());
+                entries[entry_start..entry_end]
+                    .iter()
+                    .enumerate()
+                    .all(|(j, ref_entry)| {
+                        let start = j * HASH_BYTES;
+                        let end = start + HASH_BYTES;
+                        let hash = Hash::new(&chunk[start..end]);
+                        compare_hashes(hash, ref_entry)
+                    })
+            })
+            .collect()
+    });
+    retain_simd_verify_scratch(hashes_bytes, num_hashes);
+    (res, entry_counts)
+}
+
+/// Shortest slices that `verify_cpu` hands to the AVX-512 and AVX2 paths. Slices that don't
+/// fill the last SIMD batch are padded, so these only trade off performance, not correctness.
+const MIN_AVX512_VERIFY_ENTRIES: usize = 128;
+const MIN_AVX2_VERIFY_ENTRIES: usize = 32;
+
+/// Returns whether the host supports `(avx2, avx512f)`
+fn x86_simd_features() -> (bool, bool) {
+    #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+    let (has_avx2, has_avx512) = (
+        is_x86_feature_detected!("avx2"),
+        is_x86_feature_detected!("avx512f"),
+    );
+    #[cfg(not(any(target_arch = "x86", target_arch = "x86_64")))]
+    let (has_avx2, has_avx512) = (false, false);
+    (has_avx2, has_avx512)
+}
+
+/// Timings of each CPU verification path available on the host
+#[derive(Debug, Default, PartialEq, Eq, Clone, Copy)]
+pub struct VerifyBenchmark {
+    pub generic_us: u64,
+    pub avx2_us: Option<u64>,
+    pub avx512_us: Option<u64>,
+    /// Whether every path that ran produced the same result
+    pub agreed: bool,
+}
+
+/// Implementation used to verify an entry slice's PoH
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum VerifyPath {
+    Gpu,
+    Avx512,
+    Avx2,
+    Generic,
+}
+
+/// Last PoH step of an entry, as replayed by `EntrySlice::verify_with_steps`
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum PohStep {
+    /// `num_hashes` plain hashes, the last of which is the tick. A tick with no hashes
+    /// repeats the previous hash.
+    Tick { num_hashes: u64 },
+    /// `num_hashes - 1` plain hashes, then one that mixes in the transactions hash `mixin`
+    Record { num_hashes: u64, mixin: Hash },
+}
+
+/// Result of `EntrySlice::verify_throughput`
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct ThroughputReport {
+    pub hashes_per_second: f64,
+    pub entries_per_second: f64,
+    pub path: VerifyPath,
+    pub verified: bool,
+}
+
+/// Result of `EntrySlice::verify_profile`
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct VerifyProfile {
+    pub poh_us: u64,
+    /// Time spent on the transactions part of entry verification
+    pub sig_us: u64,
+    /// `sig_us / poh_us`, treating a `poh_us` of zero as one
+    pub ratio: f64,
+    pub verified: bool,
+}
+
+impl VerifyProfile {
+    fn new(poh_us: u64, sig_us: u64, verified: bool) -> Self {
+        Self {
+            poh_us,
+            sig_us,
+            ratio: sig_us as f64 / poh_us.max(1) as f64,
+            verified,
+        }
+    }
+}
+
+/// Returns the path `verify_cpu` takes for a slice of `num_entries`
+fn cpu_verify_path(num_entries: usize) -> VerifyPath {
+    let (has_avx2, has_avx512) = x86_simd_features();
+    if api().is_none() {
+        VerifyPath::Generic
+    } else if has_avx512 && num_entries >= MIN_AVX512_VERIFY_ENTRIES {
+        VerifyPath::Avx512
+    } else if has_avx2 && num_entries >= MIN_AVX2_VERIFY_ENTRIES {
+        VerifyPath::Avx2
     } else {
-        computed_hash
-    };
-    actual == ref_entry.hash
+        VerifyPath::Generic
+    }
+}
+
+/// Checks transaction signatures on behalf of `EntrySlice::verify_with_verifier`, so that
+/// tests can substitute a stub for real signature verification.
+pub trait SignatureVerifier: Sync {
+    fn verify(&self, tx: &VersionedTransaction) -> bool;
+}
+
+/// The real ed25519 signature check
+#[derive(Debug, Default, Clone, Copy)]
+pub struct Ed25519SignatureVerifier;
+
+impl SignatureVerifier for Ed25519SignatureVerifier {
+    fn verify(&self, tx: &VersionedTransaction) -> bool {
+        tx.sanitize().is_ok() && tx.verify_and_hash_message().is_ok()
+    }
+}
+
+/// Details of a verification failure written by `EntrySlice::verify_and_dump`
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq)]
+pub struct VerifyFailureDump {
+    /// Index of the first entry whose recorded hash does not match the recomputed one
+    pub first_failing_index: usize,
+    /// The hash recorded in the failing entry
+    pub expected_hash: String,
+    /// The hash recomputed from the previous entry
+    pub actual_hash: String,
+    /// Positions within the failing entry of transactions whose signatures do not verify
+    pub failing_transactions: Vec<usize>,
+}
+
+/// An opt-in LRU cache of verification results keyed by start hash and slice fingerprint,
+/// consulted by `EntrySlice::verify_with_cache`
+pub struct VerifyCache {
+    cache: Mutex<LruCache<(Hash, Hash), bool>>,
+    hits: AtomicU64,
+    misses: AtomicU64,
+}
+
+impl VerifyCache {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            cache: Mutex::new(LruCache::new(capacity)),
+            hits: AtomicU64::default(),
+            misses: AtomicU64::default(),
+        }
+    }
+
+    pub fn hits(&self) -> u64 {
+        self.hits.load(Ordering::Relaxed)
+    }
+
+    pub fn misses(&self) -> u64 {
+        self.misses.load(Ordering::Relaxed)
+    }
 }
 
+/// Returned by `EntrySlice::verify_bounded` when a slice would need more work to verify
+/// than the caller allows
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct LimitExceeded {
+    pub num_hashes: u64,
+    pub num_signatures: u64,
+}
+
+pub type VerifyOutcome = std::result::Result<bool, LimitExceeded>;
+
 // an EntrySlice is a slice of Entries
 pub trait EntrySlice {
     /// Verifies the hashes and counts of a slice of transactions are all consistent.
     fn verify_cpu(&self, start_hash: &Hash) -> EntryVerificationState;
     fn verify_cpu_generic(&self, start_hash: &Hash) -> EntryVerificationState;
     fn verify_cpu_x86_simd(&self, start_hash: &Hash, simd_len: usize) -> EntryVerificationState;
+    /// Same as `verify_cpu_x86_simd`, but returns the result of each `simd_len` sized chunk
+    fn verify_cpu_x86_simd_per_chunk(&self, start_hash: &Hash, simd_len: usize) -> Vec<bool>;
     fn start_verify(&self, start_hash: &Hash, recyclers: VerifyRecyclers)
         -> EntryVerificationState;
+    /// Same as `start_verify`, but stages the GPU input in caller-owned pinned buffers that
+    /// can be reused across calls instead of allocating from recyclers, and verifies on the
+    /// GPU only if the slice length is within `[min_gpu_verify_entries,
+    /// max_gpu_verify_entries]`, as configured on `VerifyRecyclers`. The buffers are borrowed
+    /// for the duration of the call, so the GPU work completes before returning and the
+    /// returned state is never `Pending`. A GPU failure is left for
+    /// `EntryVerificationState::finish_verify_classified` to report as
+    /// `VerifyFailureKind::Infrastructure`.
+    fn verify_into(
+        &self,
+        start_hash: &Hash,
+        hashes_pinned: &mut PinnedVec<Hash>,
+        num_hashes_pinned: &mut PinnedVec<u64>,
+        min_gpu_verify_entries: usize,
+        max_gpu_verify_entries: usize,
+    ) -> EntryVerificationState;
     fn verify(&self, start_hash: &Hash) -> bool;
+    /// Same as `verify`, but distinguishes invalid entries from verification device failures
+    fn verify_classified(&self, start_hash: &Hash) -> std::result::Result<(), VerifyFailureKind>;
+    /// Same as `verify`, but also returns `(poh_duration_us, transaction_duration_us)`.
+    fn verify_timed(&self, start_hash: &Hash) -> (bool, u64, u64);
     /// Checks that each entry tick has the correct number of hashes. Entry slices do not
     /// necessarily end in a tick, so `tick_hash_count` is used to carry over the hash count
     /// for the next entry slice.
     fn verify_tick_hash_count(&self, tick_hash_count: &mut u64, hashes_per_tick: u64) -> bool;
     /// Counts tick entries
     fn tick_count(&self) -> u64;
+    /// Checks that the slice is exactly one full slot: `ticks_per_slot` ticks, each closing
+    /// `hashes_per_tick` hashes, with no hashes left over after the final tick.
+    fn verify_slot_tick_structure(&self, hashes_per_tick: u64, ticks_per_slot: u64) -> bool;
+    /// Summarizes each entry without cloning its transactions
+    fn summaries(&self) -> Vec<EntrySummary>;
+    /// Returns the slice with its trailing run of tick entries removed
+    fn without_trailing_ticks(&self) -> &[Entry];
+    /// Returns the index of the first of `candidate_starts` that the slice verifies against
+    fn verify_any_start(&self, candidate_starts: &[Hash]) -> Option<usize>;
+    /// Verifies the slice while collecting the static account keys of every transaction.
+    /// Keys loaded from address lookup tables are not included.
+    fn verify_collecting_accounts(&self, start_hash: &Hash) -> (bool, HashSet<Pubkey>);
+    /// Checks that no transaction's `recent_blockhash` is the hash of its own entry or of a
+    /// later entry in the slice, which would be causally impossible.
+    fn verify_no_future_blockhash(&self) -> bool;
+    /// Hash over everything PoH verification depends on: each entry's `num_hashes`, `hash`
+    /// and transaction mixin
+    fn fingerprint(&self) -> Hash;
+    /// Same as `verify`, but returns the result cached in `cache` for this start hash and
+    /// slice fingerprint if there is one, and caches the result otherwise.
+    fn verify_with_cache(&self, start_hash: &Hash, cache: &VerifyCache) -> bool;
+    /// Returns the number of leading entries that form a valid chain from `start_hash`
+    fn verifying_prefix_len(&self, start_hash: &Hash) -> usize;
+    /// Checks that no two transactions in the same entry write-lock the same account,
+    /// returning the entry index and account of the first conflict. Only static account
+    /// keys are considered, since lookup table addresses can't be resolved here.
+    fn verify_intra_entry_lock_conflicts(&self) -> std::result::Result<(), (usize, Pubkey)>;
+    /// Fills in `hashes_per_tick` for ticks whose `num_hashes` was lost (set to 0) and checks
+    /// the resulting chain from `start_hash`. Ticks whose hash equals the previous hash are
+    /// genuine zero-hash ticks and are left alone. On failure the slice is left unchanged.
+    fn infer_tick_num_hashes(&mut self, start_hash: &Hash, hashes_per_tick: u64) -> bool;
+    /// Same as `verify`, but also returns `(index, hash)` checkpoints every `checkpoint_every`
+    /// entries, where `hash` is the hash of the entry before `index`. Only checkpoints within
+    /// the verifying prefix are returned, so any of them can be passed to `verify_from_index`
+    /// to resume verification later.
+    fn verify_checkpoint(
+        &self,
+        start_hash: &Hash,
+        checkpoint_every: usize,
+    ) -> (bool, Vec<(usize, Hash)>);
+    /// Verifies the entries from `index` on, starting from a checkpointed `hash`
+    fn verify_from_index(&self, index: usize, hash: &Hash) -> bool;
+    /// Sums the compute unit limit requested by every transaction in the slice, i.e. that of
+    /// its `SetComputeUnitLimit` instruction, or the default limit for its instructions if it
+    /// has none. A cheap structural check against a block limit; compute budget instructions
+    /// are parsed but not validated.
+    fn total_requested_compute_units(&self) -> u64;
+    /// Returns every entry that doesn't chain from its recorded predecessor, or from
+    /// `start_hash` for the first entry, in slice order.
+    fn verify_failing_entries<'a>(&'a self, start_hash: &Hash) -> Vec<&'a Entry>;
+    /// Same as `verify`, but computes each entry's hash with `hasher` instead of `next_hash`.
+    /// `hasher` is passed the previous hash, the entry's `num_hashes` and, for transaction
+    /// entries, the transactions hash to mix in. Meant for cheap control-flow tests.
+    fn verify_with_hasher<H: Fn(&Hash, u64, Option<Hash>) -> Hash>(
+        &self,
+        start_hash: &Hash,
+        hasher: H,
+    ) -> bool;
+    /// Runs `verify` and reports the hashing and entry rates it achieved, along with the
+    /// path that did the work.
+    fn verify_throughput(&self, start_hash: &Hash) -> ThroughputReport;
+    /// Verifies the slice as the continuation of `parent`, i.e. `verify(&parent.hash)`
+    fn verify_following(&self, parent: &Entry) -> bool;
+    /// Same as `verify`, but splits the slice into one contiguous partition per pool in
+    /// `node_pools` and verifies each partition on its own pool, so that each NUMA node works
+    /// on its own part of the slice. Partitions chain from the recorded hash of the entry
+    /// before them.
+    fn verify_numa_aware(&self, start_hash: &Hash, node_pools: &[Arc<ThreadPool>]) -> bool;
+    /// Runs `verify` and reports its PoH and transaction durations, and their ratio, so a
+    /// scheduler can route slices between the CPU and GPU.
+    fn verify_profile(&self, start_hash: &Hash) -> VerifyProfile;
+    /// Serially recomputes each entry's hash from its recorded predecessor, in slice order,
+    /// for diffing against an independent PoH implementation.
+    fn verify_trace(&self, start_hash: &Hash) -> Vec<Hash>;
+    /// Checks that no two transactions in the same entry share a fee payer, returning the
+    /// entry index and fee payer of the first duplicate.
+    fn verify_distinct_fee_payers_per_entry(&self) -> std::result::Result<(), (usize, Pubkey)>;
+    /// Verifies the chain from `start_hash` along with the tick structure of each region of
+    /// `schedule`, whose `(start_entry_index, hashes_per_tick, ticks_per_slot)` tuples must
+    /// start at 0 and be in increasing order. Each region must consist of whole slots.
+    fn verify_variable_ticks_per_slot(
+        &self,
+        start_hash: &Hash,
+        schedule: &[(usize, u64, u64)],
+    ) -> bool;
+    /// Verifies the slice and, only if it passes, streams it to `writer` one entry at a time
+    /// in the same format as a bincode-serialized `Vec<Entry>`. Nothing is written if
+    /// verification fails.
+    fn verify_and_serialize<W: io::Write>(&self, start_hash: &Hash, writer: W) -> io::Result<bool>;
+    /// Returns the indices of entries whose `num_hashes` exceeds `factor` times the slice's
+    /// median `num_hashes`, treating a median of zero as one. Diagnostic only; such entries may
+    /// still verify.
+    fn flag_outlier_hash_counts(&self, factor: u64) -> Vec<usize>;
+    /// Size of the slice serialized as a bincode `Vec<Entry>`, computed without serializing
+    fn total_serialized_size(&self) -> u64;
+    /// Verifies the chain from `start_hash` and returns the range of entry indices of each
+    /// slot, where every `ticks_per_slot`-th tick ends a slot. Entries after the last full
+    /// slot form a final, partial range. On failure returns the index of the first entry that
+    /// doesn't verify.
+    fn verify_and_partition_slots(
+        &self,
+        start_hash: &Hash,
+        ticks_per_slot: u64,
+    ) -> std::result::Result<Vec<Range<usize>>, usize>;
+    /// Checks that every transaction serializes to at most `max_tx_bytes`, e.g.
+    /// `PACKET_DATA_SIZE`, returning the `(entry_index, transaction_index)` of the first one
+    /// that doesn't, since it could never have been sent over the wire.
+    fn verify_transaction_sizes(
+        &self,
+        max_tx_bytes: usize,
+    ) -> std::result::Result<(), (usize, usize)>;
+    /// Same as `verify_failing_entries`, but as a bitset with bit `i` set iff entry `i` links
+    /// to its recorded predecessor, or to `start_hash` for the first entry. Costs one bit per
+    /// entry, for forensics over very large slices.
+    fn verify_bitset(&self, start_hash: &Hash) -> BitVec<u64>;
+    /// Cheap sanity check, without hashing, that no entry doing work (`num_hashes > 0` or any
+    /// transactions) has the same hash as its recorded predecessor, or `start_hash` for the
+    /// first entry, which would mean the entry is corrupt.
+    fn verify_no_identity_links(&self, start_hash: &Hash) -> bool;
+    /// Same as `verify`, but mixes in `roots[i]` for entry `i` instead of recomputing the
+    /// root from its transactions, for entries whose signatures were stripped and committed
+    /// separately. A `None` root falls back to the entry's own transactions, so ticks verify
+    /// as usual. Fails if `roots` and the slice differ in length.
+    fn verify_with_committed_sig_roots(&self, start_hash: &Hash, roots: &[Option<Hash>]) -> bool;
+    /// Same as `verify`, but on a pool of at most `max_threads` threads instead of the shared
+    /// verification pool, to bound the scratch memory held at once. Pools are built on first
+    /// use and kept for later calls with the same `max_threads`. `max_threads` of 0 is treated
+    /// as 1.
+    fn verify_with_max_concurrency(&self, start_hash: &Hash, max_threads: usize) -> bool;
+    /// Whether every transaction in the slice is a vote transaction, i.e. one whose every
+    /// instruction invokes the vote program. True for a slice without transactions.
+    fn all_transactions_are_votes(&self) -> bool;
+    /// Whether any transaction in the slice is not a vote transaction, as defined by
+    /// `all_transactions_are_votes`
+    fn has_non_vote_transactions(&self) -> bool;
+    /// Fast pre-filter ahead of the bank's blockhash checks: returns the
+    /// `(entry_index, transaction_index)` of the first transaction whose `recent_blockhash`
+    /// isn't in `valid_blockhashes`.
+    fn verify_recent_blockhashes(
+        &self,
+        valid_blockhashes: &HashSet<Hash>,
+    ) -> std::result::Result<(), (usize, usize)>;
+    /// Probabilistic fast scan, e.g. of an archived ledger: fully verifies a random
+    /// `sample_rate` fraction of entries against their recorded predecessors, and only runs the
+    /// cheap checks that need no hashing on the rest, i.e. zero-hash ticks must repeat their
+    /// predecessor's hash. Not consensus-safe; a `true` result only means the slice is probably
+    /// valid. `sample_rate` must be within `[0, 1]`, and `1.0` is equivalent to `verify`.
+    fn verify_sampled(&self, start_hash: &Hash, sample_rate: f64, rng: &mut impl Rng) -> bool;
+    /// Index of the last entry with transactions, after which the slice is only ticks, or
+    /// `None` if the slice has no transactions
+    fn last_transaction_entry_index(&self) -> Option<usize>;
+    /// Same as `verify`, but also returns the PoH step replayed for each entry, i.e. whether
+    /// it was a tick or a record of its transactions, for illustrating how PoH works.
+    fn verify_with_steps(&self, start_hash: &Hash) -> (bool, Vec<PohStep>);
+    /// Same as `verify`, but accepts the recorded hash of each entry in `skip_indices` without
+    /// recomputing it, and continues the chain from it. Only for replaying ledgers with known
+    /// historical corruption at specific entries; skipped entries are trusted as is, so this
+    /// is not safe for consensus.
+    fn verify_with_exceptions(&self, start_hash: &Hash, skip_indices: &HashSet<usize>) -> bool;
+    /// Checks that no transaction lists the same account key twice in its message, returning
+    /// the `(entry_index, transaction_index)` of the first that does. Only static account keys
+    /// are considered, since lookup table addresses can't be resolved here.
+    fn verify_transaction_account_uniqueness(&self) -> std::result::Result<(), (usize, usize)>;
+    /// Counterpart of `verifying_prefix_len` for repair: returns the length of the longest
+    /// suffix whose entries after the first each verify against their recorded predecessor,
+    /// ending at `end_hash`. The first entry of the suffix only anchors the chain, since its
+    /// own predecessor isn't trusted. Returns 0 if the last entry's hash isn't `end_hash`.
+    fn verifying_suffix_len(&self, end_hash: &Hash) -> usize;
+    /// Verifies the secp256k1 and ed25519 precompile instructions of every transaction if
+    /// `secp256k1_program_enabled`, and returns whether they all verified along with the
+    /// `(entry_index, transaction_index)` of each transaction that has at least one precompile
+    /// instruction, whether or not it was verified.
+    fn verify_marking_precompiles(
+        &self,
+        secp256k1_program_enabled: bool,
+    ) -> (bool, Vec<(usize, usize)>);
+    /// Hash over the signatures of every transaction in ledger order, committing to the
+    /// global transaction order regardless of how the transactions are split into entries.
+    /// Unlike `slice_signature_root`, it also commits to which transaction each signature
+    /// belongs to, but doesn't support inclusion proofs.
+    fn transaction_order_commitment(&self) -> Hash;
+    /// Checks that no tick after the first entry has `num_hashes == 0`, which would imply
+    /// that no time elapsed between it and the previous entry.
+    fn verify_time_monotonic(&self) -> bool;
+    /// Returns `(first_index, duplicate_index)` pairs of entries that contain a transaction
+    /// with the same signature.
+    fn find_duplicate_transactions(&self) -> Vec<(usize, usize)>;
+    /// Rejects a non-empty slice in which no entry advances the hash chain from
+    /// `start_hash`, i.e. every entry is a tick with `num_hashes == 0`.
+    fn verify_progress(&self, start_hash: &Hash) -> bool;
+    /// Merkle root over the signatures of every transaction in the slice, in ledger order
+    fn slice_signature_root(&self) -> Hash;
+    /// Sanitizes every transaction, returning the `(entry_index, transaction_index)` of the
+    /// first one that fails.
+    fn verify_transaction_sanity(&self) -> std::result::Result<(), (usize, usize)>;
+    /// Runs every CPU verification path supported by the host and reports their timings
+    fn verify_benchmark(&self, start_hash: &Hash) -> VerifyBenchmark;
+    /// Estimates the work needed to verify the slice as `(num_hashes, num_signatures)`
+    fn verify_cost_estimate(&self) -> (u64, u64);
+    /// Verifies the slice only if its cost estimate is within `max_hashes` and
+    /// `max_signatures`, otherwise returns `LimitExceeded` without doing any hashing.
+    fn verify_bounded(
+        &self,
+        start_hash: &Hash,
+        max_hashes: u64,
+        max_signatures: u64,
+    ) -> VerifyOutcome;
+    /// Index of the `n`th (0-based) tick entry, or `None` if the slice has fewer ticks
+    fn nth_tick_index(&self, n: u64) -> Option<usize>;
+    /// Same as `verify`, but also checks every transaction's signatures with `verifier`
+    fn verify_with_verifier(&self, start_hash: &Hash, verifier: &dyn SignatureVerifier) -> bool;
+    /// Same as `verify`, but also returns the hash computed for each entry from its recorded
+    /// predecessor
+    fn verify_with_chain(&self, start_hash: &Hash) -> (bool, Vec<Hash>);
+    /// Same as `verify`, but on failure writes the first failing entry's expected and actual
+    /// hashes, and the indices of its transactions with bad signatures, to `path` as JSON
+    fn verify_and_dump(&self, start_hash: &Hash, path: &Path) -> io::Result<bool>;
+    /// Same as `verify`, but also checks that ticks land on `hashes_per_tick` boundaries,
+    /// starting from `tick_height`, which is advanced by the number of verified ticks
+    fn verify_with_tick_height(
+        &self,
+        start_hash: &Hash,
+        hashes_per_tick: u64,
+        tick_height: &mut u64,
+    ) -> bool;
 }
 
 impl EntrySlice for [Entry] {
@@ -638,6 +2180,21 @@ impl EntrySlice for [Entry] {
             .finish_verify()
     }
 
+    fn verify_classified(&self, start_hash: &Hash) -> std::result::Result<(), VerifyFailureKind> {
+        self.start_verify(start_hash, VerifyRecyclers::default())
+            .finish_verify_classified()
+    }
+
+    fn verify_timed(&self, start_hash: &Hash) -> (bool, u64, u64) {
+        let mut state = self.start_verify(start_hash, VerifyRecyclers::default());
+        let res = state.finish_verify();
+        (
+            res,
+            state.poh_duration_us(),
+            state.transaction_duration_us(),
+        )
+    }
+
     fn verify_cpu_generic(&self, start_hash: &Hash) -> EntryVerificationState {
         let now = Instant::now();
         let genesis = [Entry {
@@ -646,20 +2203,25 @@ impl EntrySlice for [Entry] {
             transactions: vec![],
         }];
         let entry_pairs = genesis.par_iter().chain(self).zip(self);
-        let res = PAR_THREAD_POOL.install(|| {
-            entry_pairs.all(|(x0, x1)| {
-                let r = x1.verify(&x0.hash);
-                if !r {
-                    warn!(
-                        "entry invalid!: x0: {:?}, x1: {:?} num txs: {}",
-                        x0.hash,
-                        x1.hash,
-                        x1.transactions.len()
-                    );
-                }
-                r
-            })
+        // Counts are only gathered for slices that verify, so a failure still short circuits
+        let entry_counts = install_verify_pool(|| {
+            entry_pairs
+                .map(|(x0, x1)| {
+                    let r = x1.verify(&x0.hash);
+                    if !r {
+                        warn!(
+                            target: LOG_TARGET,
+                            "entry invalid!: x0: {:?}, x1: {:?} num txs: {}",
+                            x0.hash,
+                            x1.hash,
+                            x1.transactions.len()
+                        );
+                    }
+                    r.then(|| EntryCounts::new(x1))
+                })
+                .try_reduce(EntryCounts::default, |a, b| Some(a.merge(b)))
         });
+        let res = entry_counts.is_some();
         let poh_duration_us = timing::duration_as_us(&now.elapsed());
         EntryVerificationState {
             verification_status: if res {
@@ -668,79 +2230,18 @@ impl EntrySlice for [Entry] {
                 EntryVerificationStatus::Failure
             },
             poh_duration_us,
+            transaction_duration_us: 0,
+            gpu_wait_us: 0,
+            gpu_compute_us: 0,
+            entry_counts: entry_counts.unwrap_or_default(),
             device_verification_data: DeviceVerificationData::Cpu(),
         }
     }
 
     fn verify_cpu_x86_simd(&self, start_hash: &Hash, simd_len: usize) -> EntryVerificationState {
-        use solana_sdk::hash::HASH_BYTES;
         let now = Instant::now();
-        let genesis = [Entry {
-            num_hashes: 0,
-            hash: *start_hash,
-            transactions: vec![],
-        }];
-
-        let aligned_len = ((self.len() + simd_len - 1) / simd_len) * simd_len;
-        let mut hashes_bytes = vec![0u8; HASH_BYTES * aligned_len];
-        genesis
-            .iter()
-            .chain(self)
-            .enumerate()
-            .for_each(|(i, entry)| {
-                if i < self.len() {
-                    let start = i * HASH_BYTES;
-                    let end = start + HASH_BYTES;
-                    hashes_bytes[start..end].copy_from_slice(&entry.hash.to_bytes());
-                }
-            });
-        let mut hashes_chunked: Vec<_> = hashes_bytes.chunks_mut(simd_len * HASH_BYTES).collect();
-
-        let mut num_hashes: Vec<u64> = self
-            .iter()
-            .map(|entry| entry.num_hashes.saturating_sub(1))
-            .collect();
-        num_hashes.resize(aligned_len, 0);
-        let num_hashes: Vec<_> = num_hashes.chunks(simd_len).collect();
-
-        let res = PAR_THREAD_POOL.install(|| {
-            hashes_chunked
-                .par_iter_mut()
-                .zip(num_hashes)
-                .enumerate()
-                .all(|(i, (chunk, num_hashes))| {
-                    match simd_len {
-                        8 => unsafe {
-                            (api().unwrap().poh_verify_many_simd_avx2)(
-                                chunk.as_mut_ptr(),
-                                num_hashes.as_ptr(),
-                            );
-                        },
-                        16 => unsafe {
-                            (api().unwrap().poh_verify_many_simd_avx512skx)(
-                                chunk.as_mut_ptr(),
-                                num_hashes.as_ptr(),
-                            );
-                        },
-                        _ => {
-                            panic!("unsupported simd len: {simd_len}");
-                        }
-                    }
-                    let entry_start = i * simd_len;
-                    // The last chunk may produce indexes larger than what we have in the reference entries
-                    // because it is aligned to simd_len.
-                    let entry_end = std::cmp::min(entry_start + simd_len, self.len());
-                    self[entry_start..entry_end]
-                        .iter()
-                        .enumerate()
-                        .all(|(j, ref_entry)| {
-                            let start = j * HASH_BYTES;
-                            let end = start + HASH_BYTES;
-                            let hash = Hash::new(&chunk[start..end]);
-                            compare_hashes(hash, ref_entry)
-                        })
-                })
-        });
+        let (chunk_results, entry_counts) = verify_cpu_x86_simd_chunks(self, start_hash, simd_len);
+        let res = chunk_results.into_iter().all(|chunk_res| chunk_res);
         let poh_duration_us = timing::duration_as_us(&now.elapsed());
         EntryVerificationState {
             verification_status: if res {
@@ -749,29 +2250,23 @@ impl EntrySlice for [Entry] {
                 EntryVerificationStatus::Failure
             },
             poh_duration_us,
+            transaction_duration_us: 0,
+            gpu_wait_us: 0,
+            gpu_compute_us: 0,
+            entry_counts,
             device_verification_data: DeviceVerificationData::Cpu(),
         }
     }
 
+    fn verify_cpu_x86_simd_per_chunk(&self, start_hash: &Hash, simd_len: usize) -> Vec<bool> {
+        verify_cpu_x86_simd_chunks(self, start_hash, simd_len).0
+    }
+
     fn verify_cpu(&self, start_hash: &Hash) -> EntryVerificationState {
-        #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
-        let (has_avx2, has_avx512) = (
-            is_x86_feature_detected!("avx2"),
-            is_x86_feature_detected!("avx512f"),
-        );
-        #[cfg(not(any(target_arch = "x86", target_arch = "x86_64")))]
-        let (has_avx2, has_avx512) = (false, false);
-
-        if api().is_some() {
-            if has_avx512 && self.len() >= 128 {
-                self.verify_cpu_x86_simd(start_hash, 16)
-            } else if has_avx2 && self.len() >= 48 {
-                self.verify_cpu_x86_simd(start_hash, 8)
-            } else {
-                self.verify_cpu_generic(start_hash)
-            }
-        } else {
-            self.verify_cpu_generic(start_hash)
+        match cpu_verify_path(self.len()) {
+            VerifyPath::Avx512 => self.verify_cpu_x86_simd(start_hash, 16),
+            VerifyPath::Avx2 => self.verify_cpu_x86_simd(start_hash, 8),
+            VerifyPath::Gpu | VerifyPath::Generic => self.verify_cpu_generic(start_hash),
         }
     }
 
@@ -780,91 +2275,91 @@ impl EntrySlice for [Entry] {
         start_hash: &Hash,
         recyclers: VerifyRecyclers,
     ) -> EntryVerificationState {
+        start_verify_entries(self, start_hash, recyclers, false)
+    }
+
+    fn verify_into(
+        &self,
+        start_hash: &Hash,
+        hashes_pinned: &mut PinnedVec<Hash>,
+        num_hashes_pinned: &mut PinnedVec<u64>,
+        min_gpu_verify_entries: usize,
+        max_gpu_verify_entries: usize,
+    ) -> EntryVerificationState {
+        if self.is_empty() {
+            return EntryVerificationState {
+                verification_status: EntryVerificationStatus::Success,
+                poh_duration_us: 0,
+                transaction_duration_us: 0,
+                gpu_wait_us: 0,
+                gpu_compute_us: 0,
+                entry_counts: EntryCounts::default(),
+                device_verification_data: DeviceVerificationData::Cpu(),
+            };
+        }
+        TOTAL_ENTRIES_VERIFIED.fetch_add(self.len() as u64, Ordering::Relaxed);
         let start = Instant::now();
-        let Some(api) = perf_libs::api() else {
-            return self.verify_cpu(start_hash);
+        let Some(api) = start_verify_gpu_api(self, min_gpu_verify_entries, max_gpu_verify_entries)
+        else {
+            let state = self.verify_cpu(start_hash);
+            TXS_PER_ENTRY_REPORT.record(state.entry_counts);
+            return state;
         };
-        inc_new_counter_info!("entry_verify-num_entries", self.len());
-
-        let genesis = [Entry {
-            num_hashes: 0,
-            hash: *start_hash,
-            transactions: vec![],
-        }];
-
-        let hashes: Vec<Hash> = genesis
-            .iter()
-            .chain(self)
-            .map(|entry| entry.hash)
-            .take(self.len())
-            .collect();
-
-        let mut hashes_pinned = recyclers.hash_recycler.allocate("poh_verify_hash");
-        hashes_pinned.set_pinnable();
-        hashes_pinned.resize(hashes.len(), Hash::default());
-        hashes_pinned.copy_from_slice(&hashes);
-
-        let mut num_hashes_vec = recyclers
-            .tick_count_recycler
-            .allocate("poh_verify_num_hashes");
-        num_hashes_vec.reserve_and_pin(cmp::max(1, self.len()));
-        for entry in self {
-            num_hashes_vec.push(entry.num_hashes.saturating_sub(1));
-        }
+        let entry_counts = stage_gpu_poh_verify(self, start_hash, hashes_pinned, num_hashes_pinned);
+        TXS_PER_ENTRY_REPORT.record(entry_counts);
 
         let length = self.len();
-        let hashes = Arc::new(Mutex::new(hashes_pinned));
-        let hashes_clone = hashes.clone();
-
-        let gpu_verify_thread = thread::Builder::new()
-            .name("solGpuPohVerify".into())
-            .spawn(move || {
-                let mut hashes = hashes_clone.lock().unwrap();
-                let gpu_wait = Instant::now();
-                let res;
-                unsafe {
-                    res = (api.poh_verify_many)(
-                        hashes.as_mut_ptr() as *mut u8,
-                        num_hashes_vec.as_ptr(),
-                        length,
-                        1,
-                    );
-                }
-                assert!(res == 0, "GPU PoH verify many failed");
-                inc_new_counter_info!(
-                    "entry_verify-gpu_thread",
-                    timing::duration_as_us(&gpu_wait.elapsed()) as usize
-                );
-                timing::duration_as_us(&gpu_wait.elapsed())
-            })
-            .unwrap();
-
-        let verifications = PAR_THREAD_POOL.install(|| {
-            self.into_par_iter()
-                .map(|entry| {
-                    let answer = entry.hash;
-                    let action = if entry.transactions.is_empty() {
-                        if entry.num_hashes == 0 {
-                            VerifyAction::None
-                        } else {
-                            VerifyAction::Tick
-                        }
-                    } else {
-                        VerifyAction::Mixin(hash_transactions(&entry.transactions))
+        let (gpu_result, verifications, transaction_time) = thread::scope(|scope| {
+            let hashes = &mut *hashes_pinned;
+            let num_hashes = &*num_hashes_pinned;
+            let gpu_verify_thread = thread::Builder::new()
+                .name("solGpuPohVerify".into())
+                .spawn_scoped(scope, move || {
+                    let gpu_compute = Instant::now();
+                    let res = unsafe {
+                        (api.poh_verify_many)(
+                            hashes.as_mut_ptr() as *mut u8,
+                            num_hashes.as_ptr(),
+                            length,
+                            1,
+                        )
                     };
-                    (action, answer)
+                    (res, timing::duration_as_us(&gpu_compute.elapsed()))
                 })
-                .collect()
-        });
-        let device_verification_data = DeviceVerificationData::Gpu(GpuVerificationData {
-            thread_h: Some(gpu_verify_thread),
-            verifications: Some(verifications),
-            hashes: Some(hashes),
+                .unwrap();
+
+            let mut transaction_time = Measure::start("entry_verify_transactions");
+            let verifications = verify_actions(self);
+            transaction_time.stop();
+            (gpu_verify_thread.join(), verifications, transaction_time)
         });
+        let Ok((0, gpu_compute_us)) = gpu_result else {
+            warn!(target: LOG_TARGET, "GPU PoH verify failed");
+            return EntryVerificationState {
+                verification_status: EntryVerificationStatus::Failure,
+                poh_duration_us: timing::duration_as_us(&start.elapsed()),
+                transaction_duration_us: transaction_time.as_us(),
+                gpu_wait_us: 0,
+                gpu_compute_us: 0,
+                entry_counts,
+                device_verification_data: DeviceVerificationData::DeviceFailed(),
+            };
+        };
+        inc_new_counter_info!("entry_verify-gpu_compute", gpu_compute_us as usize);
+
+        let res = check_verify_actions(&hashes_pinned[..], verifications);
         EntryVerificationState {
-            verification_status: EntryVerificationStatus::Pending,
+            verification_status: if res {
+                EntryVerificationStatus::Success
+            } else {
+                EntryVerificationStatus::Failure
+            },
             poh_duration_us: timing::duration_as_us(&start.elapsed()),
-            device_verification_data,
+            transaction_duration_us: transaction_time.as_us(),
+            gpu_wait_us: 0,
+            gpu_compute_us,
+            entry_counts,
+            device_verification_data: DeviceVerificationData::Cpu(),
         }
     }
 
@@ -879,6 +2374,7 @@ impl EntrySlice for [Entry] {
             if entry.is_tick() {
                 if *tick_hash_count != hashes_per_tick {
                     warn!(
+                        target: LOG_TARGET,
                         "invalid tick hash count!: entry: {:#?}, tick_hash_count: {}, hashes_per_tick: {}",
                         entry,
                         tick_hash_count,
@@ -895,469 +2391,4384 @@ impl EntrySlice for [Entry] {
     fn tick_count(&self) -> u64 {
         self.iter().filter(|e| e.is_tick()).count() as u64
     }
-}
 
-pub fn next_entry_mut(start: &mut Hash, num_hashes: u64, transactions: Vec<Transaction>) -> Entry {
-    let entry = Entry::new(start, num_hashes, transactions);
-    *start = entry.hash;
-    entry
-}
+    fn verify_slot_tick_structure(&self, hashes_per_tick: u64, ticks_per_slot: u64) -> bool {
+        let tick_count = self.tick_count();
+        if tick_count != ticks_per_slot {
+            warn!(
+                target: LOG_TARGET,
+                "invalid tick count: {}, ticks_per_slot: {}",
+                tick_count, ticks_per_slot
+            );
+            return false;
+        }
+        let mut tick_hash_count = 0;
+        self.verify_tick_hash_count(&mut tick_hash_count, hashes_per_tick) && tick_hash_count == 0
+    }
 
-pub fn create_ticks(num_ticks: u64, hashes_per_tick: u64, mut hash: Hash) -> Vec<Entry> {
-    repeat_with(|| next_entry_mut(&mut hash, hashes_per_tick, vec![]))
-        .take(num_ticks as usize)
-        .collect()
-}
+    fn summaries(&self) -> Vec<EntrySummary> {
+        self.iter().map(Entry::summary).collect()
+    }
 
-pub fn create_random_ticks(num_ticks: u64, max_hashes_per_tick: u64, mut hash: Hash) -> Vec<Entry> {
-    repeat_with(|| {
-        let hashes_per_tick = thread_rng().gen_range(1, max_hashes_per_tick);
-        next_entry_mut(&mut hash, hashes_per_tick, vec![])
-    })
-    .take(num_ticks as usize)
-    .collect()
-}
+    fn without_trailing_ticks(&self) -> &[Entry] {
+        let end = self
+            .iter()
+            .rposition(|entry| !entry.is_tick())
+            .map_or(0, |i| i + 1);
+        &self[..end]
+    }
 
-/// Creates the next Tick or Transaction Entry `num_hashes` after `start_hash`.
-pub fn next_entry(prev_hash: &Hash, num_hashes: u64, transactions: Vec<Transaction>) -> Entry {
-    let transactions = transactions.into_iter().map(Into::into).collect::<Vec<_>>();
-    next_versioned_entry(prev_hash, num_hashes, transactions)
-}
+    fn verify_any_start(&self, candidate_starts: &[Hash]) -> Option<usize> {
+        let Some((first, rest)) = self.split_first() else {
+            return (!candidate_starts.is_empty()).then_some(0);
+        };
+        // Only the first entry depends on the start hash, so verify the rest of the chain once
+        if !rest.verify(&first.hash) {
+            return None;
+        }
+        candidate_starts
+            .iter()
+            .position(|start_hash| first.verify(start_hash))
+    }
 
-/// Creates the next Tick or Transaction Entry `num_hashes` after `start_hash`.
-pub fn next_versioned_entry(
-    prev_hash: &Hash,
-    num_hashes: u64,
-    transactions: Vec<VersionedTransaction>,
-) -> Entry {
-    assert!(num_hashes > 0 || transactions.is_empty());
-    Entry {
-        num_hashes,
-        hash: next_hash(prev_hash, num_hashes, &transactions),
-        transactions,
+    fn verify_collecting_accounts(&self, start_hash: &Hash) -> (bool, HashSet<Pubkey>) {
+        PAR_THREAD_POOL.install(|| {
+            self.par_iter()
+                .enumerate()
+                .map(|(i, entry)| {
+                    let prev_hash = if i == 0 {
+                        start_hash
+                    } else {
+                        &self[i - 1].hash
+                    };
+                    let accounts: HashSet<Pubkey> = entry
+                        .transactions
+                        .iter()
+                        .flat_map(|tx| tx.message.static_account_keys())
+                        .copied()
+                        .collect();
+                    (entry.verify(prev_hash), accounts)
+                })
+                .reduce(
+                    || (true, HashSet::new()),
+                    |(res0, mut accounts0), (res1, accounts1)| {
+                        accounts0.extend(accounts1);
+                        (res0 && res1, accounts0)
+                    },
+                )
+        })
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use {
-        super::*,
-        solana_perf::test_tx::{test_invalid_tx, test_tx},
-        solana_sdk::{
-            hash::{hash, Hash},
-            pubkey::Pubkey,
-            signature::{Keypair, Signer},
-            system_transaction,
-            transaction::{
-                Result, SanitizedTransaction, SimpleAddressLoader, VersionedTransaction,
-            },
-        },
-    };
+    fn verify_no_future_blockhash(&self) -> bool {
+        let last_index: HashMap<&Hash, usize> = self
+            .iter()
+            .enumerate()
+            .map(|(i, entry)| (&entry.hash, i))
+            .collect();
+        self.iter().enumerate().all(|(i, entry)| {
+            entry.transactions.iter().all(|tx| {
+                let recent_blockhash = tx.message.recent_blockhash();
+                match last_index.get(recent_blockhash) {
+                    Some(&j) if j >= i => {
+                        warn!(
+                            target: LOG_TARGET,
+                            "transaction in entry {} references the hash of entry {}: {}",
+                            i, j, recent_blockhash
+                        );
+                        false
+                    }
+                    _ => true,
+                }
+            })
+        })
+    }
 
-    #[test]
-    fn test_entry_verify() {
-        let zero = Hash::default();
-        let one = hash(zero.as_ref());
-        assert!(Entry::new_tick(0, &zero).verify(&zero)); // base case, never used
-        assert!(!Entry::new_tick(0, &zero).verify(&one)); // base case, bad
-        assert!(next_entry(&zero, 1, vec![]).verify(&zero)); // inductive step
-        assert!(!next_entry(&zero, 1, vec![]).verify(&one)); // inductive step, bad
+    fn fingerprint(&self) -> Hash {
+        let mixins: Vec<Hash> = PAR_THREAD_POOL.install(|| {
+            self.par_iter()
+                .map(|entry| hash_transactions(&entry.transactions))
+                .collect()
+        });
+        let mut hasher = Hasher::default();
+        for (entry, mixin) in self.iter().zip(&mixins) {
+            hasher.hash(&entry.num_hashes.to_le_bytes());
+            hasher.hash(entry.hash.as_ref());
+            hasher.hash(mixin.as_ref());
+        }
+        hasher.result()
     }
 
-    fn test_verify_transactions(
-        entries: Vec<Entry>,
-        skip_verification: bool,
-        verify_recyclers: VerifyRecyclers,
-        verify: Arc<
-            dyn Fn(
-                    VersionedTransaction,
-                    TransactionVerificationMode,
-                ) -> Result<SanitizedTransaction>
-                + Send
-                + Sync,
-        >,
-    ) -> bool {
-        let verify_func = {
-            let verify = verify.clone();
-            let verification_mode = if skip_verification {
-                TransactionVerificationMode::HashOnly
-            } else {
-                TransactionVerificationMode::FullVerification
-            };
-            move |versioned_tx: VersionedTransaction| -> Result<SanitizedTransaction> {
-                verify(versioned_tx, verification_mode)
-            }
-        };
+    fn verify_with_cache(&self, start_hash: &Hash, cache: &VerifyCache) -> bool {
+        let key = (*start_hash, self.fingerprint());
+        if let Some(res) = cache.cache.lock().unwrap().get(&key) {
+            cache.hits.fetch_add(1, Ordering::Relaxed);
+            return *res;
+        }
+        cache.misses.fetch_add(1, Ordering::Relaxed);
+        let res = self.verify(start_hash);
+        cache.cache.lock().unwrap().put(key, res);
+        res
+    }
 
-        let cpu_verify_result = verify_transactions(entries.clone(), Arc::new(verify_func));
-        let mut gpu_verify_result: EntrySigVerificationState = {
-            let verify_result =
-                start_verify_transactions(entries, skip_verification, verify_recyclers, verify);
-            match verify_result {
-                Ok(res) => res,
-                _ => EntrySigVerificationState {
-                    verification_status: EntryVerificationStatus::Failure,
-                    entries: None,
-                    device_verification_data: DeviceSigVerificationData::Cpu(),
-                    gpu_verify_duration_us: 0,
-                },
+    fn verifying_prefix_len(&self, start_hash: &Hash) -> usize {
+        let mut prev_hash = start_hash;
+        self.iter()
+            .take_while(|entry| {
+                let res = entry.verify(prev_hash);
+                prev_hash = &entry.hash;
+                res
+            })
+            .count()
+    }
+
+    fn verify_intra_entry_lock_conflicts(&self) -> std::result::Result<(), (usize, Pubkey)> {
+        for (i, entry) in self.iter().enumerate() {
+            let mut write_locked = HashSet::new();
+            for tx in &entry.transactions {
+                let writable: HashSet<Pubkey> = tx
+                    .message
+                    .static_account_keys()
+                    .iter()
+                    .enumerate()
+                    .filter(|(index, _)| tx.message.is_maybe_writable(*index))
+                    .map(|(_, key)| *key)
+                    .collect();
+                if let Some(key) = writable.iter().find(|key| write_locked.contains(*key)) {
+                    warn!(target: LOG_TARGET, "write-lock conflict on {} in entry {}", key, i);
+                    return Err((i, *key));
+                }
+                write_locked.extend(writable);
             }
-        };
+        }
+        Ok(())
+    }
 
-        match cpu_verify_result {
-            Ok(_) => {
-                assert!(gpu_verify_result.verification_status != EntryVerificationStatus::Failure);
-                assert!(gpu_verify_result.finish_verify());
-                true
+    fn infer_tick_num_hashes(&mut self, start_hash: &Hash, hashes_per_tick: u64) -> bool {
+        let mut inferred = vec![];
+        for i in 0..self.len() {
+            let prev_hash = if i == 0 {
+                *start_hash
+            } else {
+                self[i - 1].hash
+            };
+            let entry = &mut self[i];
+            if entry.is_tick() && entry.num_hashes == 0 && entry.hash != prev_hash {
+                entry.num_hashes = hashes_per_tick;
+                inferred.push(i);
             }
-            _ => {
-                assert!(
-                    gpu_verify_result.verification_status == EntryVerificationStatus::Failure
-                        || !gpu_verify_result.finish_verify()
-                );
-                false
+        }
+        let res = self.verify(start_hash);
+        if !res {
+            for i in inferred {
+                self[i].num_hashes = 0;
             }
         }
+        res
     }
 
-    #[test]
-    fn test_entry_gpu_verify() {
-        let verify_transaction = {
-            move |versioned_tx: VersionedTransaction,
-                  verification_mode: TransactionVerificationMode|
-                  -> Result<SanitizedTransaction> {
-                let sanitized_tx = {
-                    let message_hash =
-                        if verification_mode == TransactionVerificationMode::FullVerification {
-                            versioned_tx.verify_and_hash_message()?
-                        } else {
-                            versioned_tx.message.hash()
-                        };
-
-                    SanitizedTransaction::try_create(
-                        versioned_tx,
-                        message_hash,
-                        None,
-                        SimpleAddressLoader::Disabled,
-                    )
-                }?;
-
-                Ok(sanitized_tx)
-            }
+    fn verify_checkpoint(
+        &self,
+        start_hash: &Hash,
+        checkpoint_every: usize,
+    ) -> (bool, Vec<(usize, Hash)>) {
+        let res = self.verify(start_hash);
+        if checkpoint_every == 0 {
+            return (res, vec![]);
+        }
+        let verified_len = if res {
+            self.len()
+        } else {
+            self.verifying_prefix_len(start_hash)
         };
+        let checkpoints = (checkpoint_every..self.len())
+            .step_by(checkpoint_every)
+            .take_while(|index| *index <= verified_len)
+            .map(|index| (index, self[index - 1].hash))
+            .collect();
+        (res, checkpoints)
+    }
 
-        let recycler = VerifyRecyclers::default();
+    fn verify_from_index(&self, index: usize, hash: &Hash) -> bool {
+        self.get(index..)
+            .map(|entries| entries.verify(hash))
+            .unwrap_or(false)
+    }
 
-        // Make sure we test with a number of transactions that's not a multiple of PACKETS_PER_BATCH
-        let entries_invalid = (0..1025)
-            .map(|_| {
-                let transaction = test_invalid_tx();
-                next_entry_mut(&mut Hash::default(), 0, vec![transaction])
-            })
-            .collect::<Vec<_>>();
+    fn total_requested_compute_units(&self) -> u64 {
+        self.iter()
+            .flat_map(|entry| &entry.transactions)
+            .map(requested_compute_units)
+            .fold(0, u64::saturating_add)
+    }
 
-        let entries_valid = (0..1025)
-            .map(|_| {
-                let transaction = test_tx();
-                next_entry_mut(&mut Hash::default(), 0, vec![transaction])
-            })
-            .collect::<Vec<_>>();
+    fn verify_failing_entries<'a>(&'a self, start_hash: &Hash) -> Vec<&'a Entry> {
+        PAR_THREAD_POOL.install(|| {
+            self.par_iter()
+                .enumerate()
+                .filter(|(i, entry)| {
+                    let prev_hash = if *i == 0 {
+                        start_hash
+                    } else {
+                        &self[i - 1].hash
+                    };
+                    !entry.verify(prev_hash)
+                })
+                .map(|(_, entry)| entry)
+                .collect()
+        })
+    }
 
-        assert!(!test_verify_transactions(
-            entries_invalid,
-            false,
-            recycler.clone(),
-            Arc::new(verify_transaction)
-        ));
-        assert!(test_verify_transactions(
-            entries_valid,
-            false,
-            recycler,
-            Arc::new(verify_transaction)
-        ));
+    fn verify_with_hasher<H: Fn(&Hash, u64, Option<Hash>) -> Hash>(
+        &self,
+        start_hash: &Hash,
+        hasher: H,
+    ) -> bool {
+        let mut prev_hash = start_hash;
+        self.iter().all(|entry| {
+            let mixin = (!entry.is_tick()).then(|| hash_transactions(&entry.transactions));
+            let res = hashes_equal(&hasher(prev_hash, entry.num_hashes, mixin), &entry.hash);
+            prev_hash = &entry.hash;
+            res
+        })
     }
 
-    #[test]
-    fn test_transaction_reorder_attack() {
-        let zero = Hash::default();
+    fn verify_throughput(&self, start_hash: &Hash) -> ThroughputReport {
+        let path = if !self.is_empty()
+            && gpu_api().is_some()
+            && self.len() >= DEFAULT_MIN_GPU_VERIFY_ENTRIES
+        {
+            VerifyPath::Gpu
+        } else {
+            cpu_verify_path(self.len())
+        };
+        let num_hashes = self
+            .iter()
+            .fold(0u64, |total, entry| total.saturating_add(entry.num_hashes));
+
+        let start = Instant::now();
+        let verified = self.verify(start_hash);
+        // Avoid dividing by zero on slices too small to measure
+        let elapsed_secs = start.elapsed().as_secs_f64().max(f64::MIN_POSITIVE);
+
+        ThroughputReport {
+            hashes_per_second: num_hashes as f64 / elapsed_secs,
+            entries_per_second: self.len() as f64 / elapsed_secs,
+            path,
+            verified,
+        }
+    }
+
+    fn verify_following(&self, parent: &Entry) -> bool {
+        self.verify(&parent.hash)
+    }
+
+    fn verify_numa_aware(&self, start_hash: &Hash, node_pools: &[Arc<ThreadPool>]) -> bool {
+        if node_pools.is_empty() || self.is_empty() {
+            return self.verify(start_hash);
+        }
+        let partition_len = (self.len() + node_pools.len() - 1) / node_pools.len();
+        thread::scope(|scope| {
+            let handles: Vec<_> = self
+                .chunks(partition_len)
+                .zip(node_pools)
+                .enumerate()
+                .map(|(i, (partition, pool))| {
+                    let partition_start_hash = if i == 0 {
+                        start_hash
+                    } else {
+                        &self[i * partition_len - 1].hash
+                    };
+                    scope.spawn(move || {
+                        pool.install(|| {
+                            partition.par_iter().enumerate().all(|(j, entry)| {
+                                let prev_hash = if j == 0 {
+                                    partition_start_hash
+                                } else {
+                                    &partition[j - 1].hash
+                                };
+                                entry.verify(prev_hash)
+                            })
+                        })
+                    })
+                })
+                .collect();
+            // Any partitions left unjoined are joined when the scope ends
+            handles.into_iter().all(|handle| handle.join().unwrap())
+        })
+    }
+
+    fn verify_profile(&self, start_hash: &Hash) -> VerifyProfile {
+        let mut state = self.start_verify(start_hash, VerifyRecyclers::default());
+        let verified = state.finish_verify();
+        VerifyProfile::new(
+            state.poh_duration_us(),
+            state.transaction_duration_us(),
+            verified,
+        )
+    }
+
+    fn verify_trace(&self, start_hash: &Hash) -> Vec<Hash> {
+        let mut prev_hash = start_hash;
+        self.iter()
+            .map(|entry| {
+                let hash = entry.expected_hash(prev_hash);
+                prev_hash = &entry.hash;
+                hash
+            })
+            .collect()
+    }
+
+    fn verify_distinct_fee_payers_per_entry(&self) -> std::result::Result<(), (usize, Pubkey)> {
+        for (i, entry) in self.iter().enumerate() {
+            let mut fee_payers = HashSet::new();
+            for fee_payer in entry
+                .transactions
+                .iter()
+                .filter_map(|tx| tx.message.static_account_keys().first())
+            {
+                if !fee_payers.insert(fee_payer) {
+                    warn!(target: LOG_TARGET, "fee payer {} repeated in entry {}", fee_payer, i);
+                    return Err((i, *fee_payer));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn verify_variable_ticks_per_slot(
+        &self,
+        start_hash: &Hash,
+        schedule: &[(usize, u64, u64)],
+    ) -> bool {
+        let starts_valid = schedule.first().map(|(start, _, _)| *start) == Some(0)
+            && schedule.windows(2).all(|pair| pair[0].0 < pair[1].0)
+            && schedule.iter().all(|(start, _, _)| *start <= self.len());
+        if !starts_valid {
+            warn!(target: LOG_TARGET, "invalid tick schedule: {:?}", schedule);
+            return false;
+        }
+
+        let region_ends = schedule
+            .iter()
+            .skip(1)
+            .map(|(start, _, _)| *start)
+            .chain(std::iter::once(self.len()));
+        for (&(start, hashes_per_tick, ticks_per_slot), end) in schedule.iter().zip(region_ends) {
+            let region = &self[start..end];
+            let mut slot_start = 0;
+            let mut num_ticks = 0;
+            for (i, entry) in region.iter().enumerate() {
+                if !entry.is_tick() {
+                    continue;
+                }
+                num_ticks += 1;
+                if num_ticks == ticks_per_slot {
+                    if !region[slot_start..=i]
+                        .verify_slot_tick_structure(hashes_per_tick, ticks_per_slot)
+                    {
+                        return false;
+                    }
+                    slot_start = i + 1;
+                    num_ticks = 0;
+                }
+            }
+            if slot_start != region.len() {
+                warn!(
+                    target: LOG_TARGET,
+                    "region starting at entry {} does not end on a slot boundary",
+                    start
+                );
+                return false;
+            }
+        }
+        self.verify(start_hash)
+    }
+
+    fn verify_and_serialize<W: io::Write>(
+        &self,
+        start_hash: &Hash,
+        mut writer: W,
+    ) -> io::Result<bool> {
+        if !self.verify(start_hash) {
+            return Ok(false);
+        }
+        let into_io_error = |err| io::Error::new(io::ErrorKind::Other, err);
+        bincode::serialize_into(&mut writer, &(self.len() as u64)).map_err(into_io_error)?;
+        for entry in self {
+            bincode::serialize_into(&mut writer, entry).map_err(into_io_error)?;
+        }
+        Ok(true)
+    }
+
+    fn flag_outlier_hash_counts(&self, factor: u64) -> Vec<usize> {
+        if self.is_empty() {
+            return vec![];
+        }
+        let mut num_hashes: Vec<u64> = self.iter().map(|entry| entry.num_hashes).collect();
+        let median_index = num_hashes.len() / 2;
+        let (_, median, _) = num_hashes.select_nth_unstable(median_index);
+        let limit = factor.saturating_mul((*median).max(1));
+        self.iter()
+            .enumerate()
+            .filter(|(_, entry)| entry.num_hashes > limit)
+            .map(|(i, _)| i)
+            .collect()
+    }
+
+    fn total_serialized_size(&self) -> u64 {
+        // Length prefix of the serialized Vec<Entry>
+        let len_prefix_bytes = std::mem::size_of::<u64>() as u64;
+        self.iter().fold(len_prefix_bytes, |total, entry| {
+            total.saturating_add(bincode::serialized_size(entry).expect("serialize entry"))
+        })
+    }
+
+    fn verify_and_partition_slots(
+        &self,
+        start_hash: &Hash,
+        ticks_per_slot: u64,
+    ) -> std::result::Result<Vec<Range<usize>>, usize> {
+        if !self.verify(start_hash) {
+            return Err(self.verifying_prefix_len(start_hash));
+        }
+        let mut slots = vec![];
+        let mut slot_start = 0;
+        let mut num_ticks = 0;
+        for (i, entry) in self.iter().enumerate() {
+            if entry.is_tick() {
+                num_ticks += 1;
+                if num_ticks == ticks_per_slot {
+                    slots.push(slot_start..i + 1);
+                    slot_start = i + 1;
+                    num_ticks = 0;
+                }
+            }
+        }
+        if slot_start < self.len() {
+            slots.push(slot_start..self.len());
+        }
+        Ok(slots)
+    }
+
+    fn verify_transaction_sizes(
+        &self,
+        max_tx_bytes: usize,
+    ) -> std::result::Result<(), (usize, usize)> {
+        for (i, entry) in self.iter().enumerate() {
+            for (j, tx) in entry.transactions.iter().enumerate() {
+                let size = bincode::serialized_size(tx).expect("serialize transaction");
+                if size > max_tx_bytes as u64 {
+                    warn!(
+                        target: LOG_TARGET,
+                        "transaction {} of entry {} is {} bytes, limit {}",
+                        j,
+                        i,
+                        size,
+                        max_tx_bytes
+                    );
+                    return Err((i, j));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn verify_bitset(&self, start_hash: &Hash) -> BitVec<u64> {
+        const BITS_PER_BLOCK: usize = u64::BITS as usize;
+        let blocks: Vec<u64> = PAR_THREAD_POOL.install(|| {
+            self.par_chunks(BITS_PER_BLOCK)
+                .enumerate()
+                .map(|(chunk_index, chunk)| {
+                    let first = chunk_index * BITS_PER_BLOCK;
+                    let mut prev_hash = if first == 0 {
+                        start_hash
+                    } else {
+                        &self[first - 1].hash
+                    };
+                    chunk.iter().enumerate().fold(0, |block, (i, entry)| {
+                        let ok = entry.verify(prev_hash);
+                        prev_hash = &entry.hash;
+                        block | (u64::from(ok) << i)
+                    })
+                })
+                .collect()
+        });
+        let mut bits: BitVec<u64> = blocks.into();
+        bits.truncate(self.len() as u64);
+        bits
+    }
+
+    fn verify_no_identity_links(&self, start_hash: &Hash) -> bool {
+        let mut prev_hash = start_hash;
+        self.iter().enumerate().all(|(i, entry)| {
+            let does_work = entry.num_hashes > 0 || !entry.transactions.is_empty();
+            let ok = !(does_work && entry.hash == *prev_hash);
+            if !ok {
+                warn!(
+                    target: LOG_TARGET,
+                    "entry {} repeats its predecessor's hash {}",
+                    i,
+                    prev_hash
+                );
+            }
+            prev_hash = &entry.hash;
+            ok
+        })
+    }
+
+    fn verify_with_committed_sig_roots(&self, start_hash: &Hash, roots: &[Option<Hash>]) -> bool {
+        if roots.len() != self.len() {
+            warn!(
+                target: LOG_TARGET,
+                "{} committed roots for {} entries",
+                roots.len(),
+                self.len()
+            );
+            return false;
+        }
+        let genesis = [Entry {
+            num_hashes: 0,
+            hash: *start_hash,
+            transactions: vec![],
+        }];
+        let entry_pairs = genesis.par_iter().chain(self).zip(self).zip(roots);
+        PAR_THREAD_POOL.install(|| {
+            entry_pairs.all(|((prev, entry), root)| {
+                let ok = match root {
+                    Some(root) => hashes_equal(
+                        &next_hash_with_mixin(&prev.hash, entry.num_hashes, Some(*root)),
+                        &entry.hash,
+                    ),
+                    None => entry.verify(&prev.hash),
+                };
+                if !ok {
+                    warn!(
+                        target: LOG_TARGET,
+                        "entry invalid with committed root {:?}: prev {:?} entry hash {:?}",
+                        root,
+                        prev.hash,
+                        entry.hash
+                    );
+                }
+                ok
+            })
+        })
+    }
+
+    fn verify_with_max_concurrency(&self, start_hash: &Hash, max_threads: usize) -> bool {
+        capped_thread_pool(max_threads.max(1)).install(|| self.verify(start_hash))
+    }
+
+    fn all_transactions_are_votes(&self) -> bool {
+        !self.has_non_vote_transactions()
+    }
+
+    fn has_non_vote_transactions(&self) -> bool {
+        self.iter()
+            .flat_map(|entry| &entry.transactions)
+            .any(|tx| !is_vote_transaction(tx))
+    }
+
+    fn verify_recent_blockhashes(
+        &self,
+        valid_blockhashes: &HashSet<Hash>,
+    ) -> std::result::Result<(), (usize, usize)> {
+        for (i, entry) in self.iter().enumerate() {
+            for (j, tx) in entry.transactions.iter().enumerate() {
+                let recent_blockhash = tx.message.recent_blockhash();
+                if !valid_blockhashes.contains(recent_blockhash) {
+                    warn!(
+                        target: LOG_TARGET,
+                        "transaction {} of entry {} references unknown blockhash {}",
+                        j, i, recent_blockhash
+                    );
+                    return Err((i, j));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn verify_sampled(&self, start_hash: &Hash, sample_rate: f64, rng: &mut impl Rng) -> bool {
+        assert!(
+            (0.0..=1.0).contains(&sample_rate),
+            "sample_rate out of range: {sample_rate}"
+        );
+        let sampled: Vec<bool> = repeat_with(|| rng.gen_bool(sample_rate))
+            .take(self.len())
+            .collect();
+        PAR_THREAD_POOL.install(|| {
+            self.par_iter()
+                .zip(sampled)
+                .enumerate()
+                .all(|(i, (entry, sampled))| {
+                    let prev_hash = if i == 0 {
+                        start_hash
+                    } else {
+                        &self[i - 1].hash
+                    };
+                    if sampled {
+                        entry.verify(prev_hash)
+                    } else if entry.num_hashes == 0 && entry.is_tick() {
+                        entry.hash == *prev_hash
+                    } else {
+                        true
+                    }
+                })
+        })
+    }
+
+    fn last_transaction_entry_index(&self) -> Option<usize> {
+        self.iter().rposition(|entry| !entry.is_tick())
+    }
+
+    fn verify_with_steps(&self, start_hash: &Hash) -> (bool, Vec<PohStep>) {
+        let results: Vec<(bool, PohStep)> = PAR_THREAD_POOL.install(|| {
+            self.par_iter()
+                .enumerate()
+                .map(|(i, entry)| {
+                    let prev_hash = if i == 0 {
+                        start_hash
+                    } else {
+                        &self[i - 1].hash
+                    };
+                    let num_hashes = entry.num_hashes;
+                    if entry.is_tick() {
+                        let step = PohStep::Tick { num_hashes };
+                        return (entry.verify(prev_hash), step);
+                    }
+                    let mixin = hash_transactions(&entry.transactions);
+                    let step = PohStep::Record { num_hashes, mixin };
+                    let hash = next_hash_with_mixin(prev_hash, num_hashes, Some(mixin));
+                    (hashes_equal(&hash, &entry.hash), step)
+                })
+                .collect()
+        });
+        let verified = results.iter().all(|(ok, _)| *ok);
+        let steps = results.into_iter().map(|(_, step)| step).collect();
+        (verified, steps)
+    }
+
+    fn verify_with_exceptions(&self, start_hash: &Hash, skip_indices: &HashSet<usize>) -> bool {
+        PAR_THREAD_POOL.install(|| {
+            self.par_iter().enumerate().all(|(i, entry)| {
+                if skip_indices.contains(&i) {
+                    warn!(
+                        target: LOG_TARGET,
+                        "skipping verification of entry {}: {}", i, entry.hash
+                    );
+                    return true;
+                }
+                let prev_hash = if i == 0 {
+                    start_hash
+                } else {
+                    &self[i - 1].hash
+                };
+                entry.verify(prev_hash)
+            })
+        })
+    }
+
+    fn verify_transaction_account_uniqueness(&self) -> std::result::Result<(), (usize, usize)> {
+        for (i, entry) in self.iter().enumerate() {
+            for (j, tx) in entry.transactions.iter().enumerate() {
+                let account_keys = tx.message.static_account_keys();
+                let mut unique_keys = HashSet::with_capacity(account_keys.len());
+                if let Some(key) = account_keys.iter().find(|key| !unique_keys.insert(*key)) {
+                    warn!(
+                        target: LOG_TARGET,
+                        "transaction {} of entry {} lists account {} more than once",
+                        j,
+                        i,
+                        key
+                    );
+                    return Err((i, j));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn verifying_suffix_len(&self, end_hash: &Hash) -> usize {
+        match self.last() {
+            Some(last) if last.hash == *end_hash => {}
+            _ => return 0,
+        }
+        let verified_links = self
+            .windows(2)
+            .rev()
+            .take_while(|pair| pair[1].verify(&pair[0].hash))
+            .count();
+        verified_links + 1
+    }
+
+    fn verify_marking_precompiles(
+        &self,
+        secp256k1_program_enabled: bool,
+    ) -> (bool, Vec<(usize, usize)>) {
+        let mut verified = true;
+        let mut marked = vec![];
+        for (i, entry) in self.iter().enumerate() {
+            for (j, tx) in entry.transactions.iter().enumerate() {
+                let has_precompile = verify_precompile_instructions(
+                    tx,
+                    secp256k1_program_enabled,
+                    secp256k1_program_enabled,
+                )
+                .unwrap_or_else(|err| {
+                    warn!(
+                        target: LOG_TARGET,
+                        "transaction {} of entry {} failed precompile verification: {}", j, i, err
+                    );
+                    verified = false;
+                    // Only precompile instructions are verified
+                    true
+                });
+                if has_precompile {
+                    marked.push((i, j));
+                }
+            }
+        }
+        (verified, marked)
+    }
+
+    fn transaction_order_commitment(&self) -> Hash {
+        let mut hasher = Hasher::default();
+        for tx in self.iter().flat_map(|entry| &entry.transactions) {
+            // Prefix each transaction's signatures with their count, so that signatures can't
+            // be shifted between neighbouring transactions without changing the commitment
+            hasher.hash(&(tx.signatures.len() as u64).to_le_bytes());
+            for signature in &tx.signatures {
+                hasher.hash(signature.as_ref());
+            }
+        }
+        hasher.result()
+    }
+
+    fn verify_time_monotonic(&self) -> bool {
+        // The first entry may be a zero-hash genesis tick
+        self.iter().enumerate().skip(1).all(|(i, entry)| {
+            let ok = !(entry.is_tick() && entry.num_hashes == 0);
+            if !ok {
+                warn!(target: LOG_TARGET, "zero-hash tick at entry index {}", i);
+            }
+            ok
+        })
+    }
+
+    fn find_duplicate_transactions(&self) -> Vec<(usize, usize)> {
+        let mut first_seen = HashMap::new();
+        let mut duplicates = vec![];
+        for (i, entry) in self.iter().enumerate() {
+            for signature in entry
+                .transactions
+                .iter()
+                .filter_map(|tx| tx.signatures.first())
+            {
+                let first = *first_seen.entry(signature).or_insert(i);
+                if first != i {
+                    duplicates.push((first, i));
+                }
+            }
+        }
+        duplicates
+    }
+
+    fn verify_progress(&self, start_hash: &Hash) -> bool {
+        if self.is_empty() {
+            return true;
+        }
+        let res = self
+            .iter()
+            .any(|entry| entry.num_hashes > 0 || !entry.transactions.is_empty());
+        if !res {
+            warn!(
+                target: LOG_TARGET,
+                "no progress from {:?} across {} entries",
+                start_hash,
+                self.len()
+            );
+        }
+        res
+    }
+
+    fn slice_signature_root(&self) -> Hash {
+        let signatures: Vec<_> = self
+            .iter()
+            .flat_map(|entry| entry.transactions.iter())
+            .flat_map(|tx| tx.signatures.iter())
+            .collect();
+        let merkle_tree = MerkleTree::new(&signatures);
+        if let Some(root_hash) = merkle_tree.get_root() {
+            *root_hash
+        } else {
+            Hash::default()
+        }
+    }
+
+    fn verify_transaction_sanity(&self) -> std::result::Result<(), (usize, usize)> {
+        for (i, entry) in self.iter().enumerate() {
+            for (j, tx) in entry.transactions.iter().enumerate() {
+                if let Err(err) = tx.sanitize() {
+                    warn!(
+                        target: LOG_TARGET,
+                        "transaction {} in entry {} failed sanitize: {:?}",
+                        j, i, err
+                    );
+                    return Err((i, j));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn verify_benchmark(&self, start_hash: &Hash) -> VerifyBenchmark {
+        let mut generic = self.verify_cpu_generic(start_hash);
+        let expected = generic.finish_verify();
+        let mut agreed = true;
+
+        let (has_avx2, has_avx512) = x86_simd_features();
+        let has_api = api().is_some();
+        let mut run_simd = |supported: bool, simd_len: usize| {
+            (has_api && supported).then(|| {
+                let mut state = self.verify_cpu_x86_simd(start_hash, simd_len);
+                agreed &= state.finish_verify() == expected;
+                state.poh_duration_us()
+            })
+        };
+        let avx2_us = run_simd(has_avx2, 8);
+        let avx512_us = run_simd(has_avx512, 16);
+
+        VerifyBenchmark {
+            generic_us: generic.poh_duration_us(),
+            avx2_us,
+            avx512_us,
+            agreed,
+        }
+    }
+
+    fn verify_cost_estimate(&self) -> (u64, u64) {
+        self.iter()
+            .fold((0, 0), |(num_hashes, num_signatures), entry| {
+                (
+                    num_hashes.saturating_add(entry.num_hashes),
+                    entry.transactions.iter().fold(num_signatures, |acc, tx| {
+                        acc.saturating_add(tx.signatures.len() as u64)
+                    }),
+                )
+            })
+    }
+
+    fn verify_bounded(
+        &self,
+        start_hash: &Hash,
+        max_hashes: u64,
+        max_signatures: u64,
+    ) -> VerifyOutcome {
+        let (num_hashes, num_signatures) = self.verify_cost_estimate();
+        if num_hashes > max_hashes || num_signatures > max_signatures {
+            warn!(
+                target: LOG_TARGET,
+                "verify limit exceeded: hashes: {}/{}, signatures: {}/{}",
+                num_hashes, max_hashes, num_signatures, max_signatures
+            );
+            return Err(LimitExceeded {
+                num_hashes,
+                num_signatures,
+            });
+        }
+        Ok(self.verify(start_hash))
+    }
+
+    fn nth_tick_index(&self, n: u64) -> Option<usize> {
+        self.iter()
+            .enumerate()
+            .filter(|(_, entry)| entry.is_tick())
+            .nth(usize::try_from(n).ok()?)
+            .map(|(i, _)| i)
+    }
+
+    fn verify_with_verifier(&self, start_hash: &Hash, verifier: &dyn SignatureVerifier) -> bool {
+        if !self.verify_cpu(start_hash).finish_verify() {
+            return false;
+        }
+        PAR_THREAD_POOL.install(|| {
+            self.par_iter().enumerate().all(|(i, entry)| {
+                let res = entry.transactions.iter().all(|tx| verifier.verify(tx));
+                if !res {
+                    warn!(target: LOG_TARGET, "signature verification failed in entry {}", i);
+                }
+                res
+            })
+        })
+    }
+
+    fn verify_with_chain(&self, start_hash: &Hash) -> (bool, Vec<Hash>) {
+        let chain: Vec<Hash> = PAR_THREAD_POOL.install(|| {
+            self.par_iter()
+                .enumerate()
+                .map(|(i, entry)| {
+                    let prev_hash = if i == 0 {
+                        start_hash
+                    } else {
+                        &self[i - 1].hash
+                    };
+                    entry.expected_hash(prev_hash)
+                })
+                .collect()
+        });
+        let res = chain
+            .iter()
+            .zip(self)
+            .all(|(computed, entry)| hashes_equal(computed, &entry.hash));
+        (res, chain)
+    }
+
+    fn verify_and_dump(&self, start_hash: &Hash, path: &Path) -> io::Result<bool> {
+        let (res, chain) = self.verify_with_chain(start_hash);
+        if res {
+            return Ok(true);
+        }
+        let Some((first_failing_index, (actual, entry))) = chain
+            .iter()
+            .zip(self)
+            .enumerate()
+            .find(|(_, (computed, entry))| !hashes_equal(computed, &entry.hash))
+        else {
+            return Ok(false);
+        };
+        let failing_transactions = entry
+            .transactions
+            .iter()
+            .enumerate()
+            .filter(|(_, tx)| !tx.verify_with_results().iter().all(|ok| *ok))
+            .map(|(i, _)| i)
+            .collect();
+        let dump = VerifyFailureDump {
+            first_failing_index,
+            expected_hash: entry.hash.to_string(),
+            actual_hash: actual.to_string(),
+            failing_transactions,
+        };
+        warn!(
+            target: LOG_TARGET,
+            "entry {} failed verification, writing details to {:?}",
+            first_failing_index, path
+        );
+        serde_json::to_writer_pretty(File::create(path)?, &dump)?;
+        Ok(false)
+    }
+
+    fn verify_with_tick_height(
+        &self,
+        start_hash: &Hash,
+        hashes_per_tick: u64,
+        tick_height: &mut u64,
+    ) -> bool {
+        // Poh requires more than one hash per tick; anything less disables tick boundaries
+        let hashes_per_tick = (hashes_per_tick > 1).then_some(hashes_per_tick);
+        let mut poh = Poh::new_with_slot_info(*start_hash, hashes_per_tick, *tick_height);
+        for (i, entry) in self.iter().enumerate() {
+            if entry.is_tick() && entry.num_hashes == 0 {
+                if poh.hash != entry.hash {
+                    warn!(
+                        target: LOG_TARGET,
+                        "zero-hash tick {} does not match the previous hash",
+                        i
+                    );
+                    return false;
+                }
+                continue;
+            }
+            poh.hash(entry.num_hashes.saturating_sub(1));
+            let poh_entry = if entry.is_tick() {
+                poh.tick()
+            } else {
+                poh.record(hash_transactions(&entry.transactions))
+            };
+            match &poh_entry {
+                Some(poh_entry)
+                    if poh_entry.hash == entry.hash && poh_entry.num_hashes == entry.num_hashes => {
+                }
+                _ => {
+                    warn!(
+                        target: LOG_TARGET,
+                        "entry {} invalid at tick height {}: {:?}",
+                        i, tick_height, poh_entry
+                    );
+                    return false;
+                }
+            }
+            if entry.is_tick() {
+                *tick_height += 1;
+            }
+        }
+        true
+    }
+}
+
+/// Content hash of the serialized entries, used to dedup entry batches
+pub fn entries_content_hash(entries: &[Entry]) -> Hash {
+    let bytes = bincode::serialize(entries).expect("serialize entries");
+    hashv(&[&bytes])
+}
+
+/// Times `hash_transactions` over `count` single-signature transactions for each of
+/// `counts`, to help tune where Merkle root construction gets expensive.
+pub fn bench_hash_transactions(counts: &[usize]) -> Vec<(usize, Duration)> {
+    counts
+        .iter()
+        .map(|&count| {
+            let transactions: Vec<_> = repeat_with(|| VersionedTransaction {
+                signatures: vec![solana_sdk::signature::Signature::new_unique()],
+                ..VersionedTransaction::default()
+            })
+            .take(count)
+            .collect();
+            let start = Instant::now();
+            let root = hash_transactions(&transactions);
+            let elapsed = start.elapsed();
+            std::hint::black_box(root);
+            (count, elapsed)
+        })
+        .collect()
+}
+
+/// Verifies two branches that diverge after a common prefix, each from the prefix's last
+/// hash, returning the validity of each branch
+pub fn verify_fork(
+    common_last_hash: &Hash,
+    branch_a: &[Entry],
+    branch_b: &[Entry],
+) -> (bool, bool) {
+    (
+        branch_a.verify(common_last_hash),
+        branch_b.verify(common_last_hash),
+    )
+}
+
+/// Verifies that `num_ticks` ticks of `hashes_per_tick` hashes each, with no transactions,
+/// lead from `start_hash` to `expected_last`. Only the final hash is known, so the run is a
+/// single serial hash chain. The SIMD kernels hash independent chains side by side and can't
+/// split one chain across lanes, so this hashes on the CPU even where they are available.
+pub fn verify_tick_run(
+    start_hash: &Hash,
+    num_ticks: u64,
+    hashes_per_tick: u64,
+    expected_last: &Hash,
+) -> bool {
+    let last_hash = (0..num_ticks).fold(*start_hash, |hash, _| {
+        next_hash(&hash, hashes_per_tick, &[])
+    });
+    hashes_equal(&last_hash, expected_last)
+}
+
+/// Splits `entries` into chunks that each serialize as a `Vec<Entry>` of at most
+/// `max_chunk_bytes`, including the length prefix. Entries are never split across chunks; an
+/// entry that doesn't fit within the limit on its own gets a chunk to itself.
+pub fn entries_to_serialized_chunks(entries: &[Entry], max_chunk_bytes: usize) -> Vec<Vec<u8>> {
+    // Length prefix of the serialized Vec<Entry>
+    const LEN_PREFIX_BYTES: usize = std::mem::size_of::<u64>();
+    let serialize = |entries: &[Entry]| bincode::serialize(entries).expect("serialize entries");
+
+    let mut chunks = vec![];
+    let mut chunk_start = 0;
+    let mut chunk_bytes = LEN_PREFIX_BYTES;
+    for (i, entry) in entries.iter().enumerate() {
+        let entry_bytes = bincode::serialized_size(entry).expect("serialize entry") as usize;
+        if i > chunk_start && chunk_bytes + entry_bytes > max_chunk_bytes {
+            chunks.push(serialize(&entries[chunk_start..i]));
+            chunk_start = i;
+            chunk_bytes = LEN_PREFIX_BYTES;
+        }
+        chunk_bytes += entry_bytes;
+    }
+    if chunk_start < entries.len() {
+        chunks.push(serialize(&entries[chunk_start..]));
+    }
+    chunks
+}
+
+/// Verifies a bincode-serialized `Vec<Entry>` from `start_hash` one entry at a time, without
+/// deserializing the whole buffer first. On failure returns the byte offset in `bytes` at
+/// which the first entry that doesn't verify, or can't be deserialized, starts. A buffer
+/// whose length prefix can't be read fails at offset 0.
+pub fn verify_serialized(bytes: &[u8], start_hash: Hash) -> std::result::Result<(), u64> {
+    let mut cursor = io::Cursor::new(bytes);
+    let num_entries: u64 = bincode::deserialize_from(&mut cursor).map_err(|_| 0u64)?;
+    let mut prev_hash = start_hash;
+    for i in 0..num_entries {
+        let offset = cursor.position();
+        let ok = match bincode::deserialize_from::<_, Entry>(&mut cursor) {
+            Ok(entry) => {
+                let ok = entry.verify(&prev_hash);
+                prev_hash = entry.hash;
+                ok
+            }
+            Err(err) => {
+                warn!(target: LOG_TARGET, "unable to deserialize entry {}: {}", i, err);
+                false
+            }
+        };
+        if !ok {
+            warn!(target: LOG_TARGET, "entry {} at byte offset {} failed", i, offset);
+            return Err(offset);
+        }
+    }
+    Ok(())
+}
+
+/// Spawns a thread that verifies each batch received on `input`, chaining from `start_hash`,
+/// and forwards only the batches that verify. Failed batches are logged and dropped, and the
+/// chain continues from the last forwarded entry.
+pub fn verify_filter(input: EntryReceiver, start_hash: Hash) -> EntryReceiver {
+    let (sender, receiver) = unbounded();
+    thread::Builder::new()
+        .name("solEntryFilter".into())
+        .spawn(move || {
+            let mut last_hash = start_hash;
+            for entries in input.iter() {
+                if !entries.verify(&last_hash) {
+                    warn!(
+                        target: LOG_TARGET,
+                        "dropping batch of {} entries that failed verification",
+                        entries.len()
+                    );
+                    continue;
+                }
+                if let Some(last) = entries.last() {
+                    last_hash = last.hash;
+                }
+                if sender.send(entries).is_err() {
+                    break;
+                }
+            }
+        })
+        .unwrap();
+    receiver
+}
+
+/// Verifies each batch from `stream` on tokio's blocking pool, chaining the hash from
+/// `start_hash` across batches. Returns false as soon as a batch fails.
+#[cfg(feature = "async")]
+pub async fn verify_stream_async<S: futures::Stream<Item = Vec<Entry>>>(
+    stream: S,
+    start_hash: Hash,
+) -> bool {
+    use futures::StreamExt;
+
+    futures::pin_mut!(stream);
+    let mut last_hash = start_hash;
+    while let Some(entries) = stream.next().await {
+        let Some(batch_last_hash) = entries.last().map(|entry| entry.hash) else {
+            continue;
+        };
+        let res = tokio::task::spawn_blocking(move || entries.verify(&last_hash))
+            .await
+            .expect("entry verify task");
+        if !res {
+            warn!(target: LOG_TARGET, "entry batch from stream failed verification");
+            return false;
+        }
+        last_hash = batch_last_hash;
+    }
+    true
+}
+
+/// Verifies a bincode-serialized `Vec<Entry>` stored at `path`, chaining from `start_hash`.
+/// The file is memory-mapped and entries are deserialized and verified one at a time, so
+/// the whole slice is never materialized. Truncated or trailing data is reported as an
+/// `InvalidData` error.
+pub fn verify_mmap(path: &Path, start_hash: Hash) -> io::Result<bool> {
+    let file = File::open(path)?;
+    let mmap = unsafe { Mmap::map(&file)? };
+    let mut reader = &mmap[..];
+    let invalid_data = |err| io::Error::new(io::ErrorKind::InvalidData, err);
+
+    let num_entries: u64 = bincode::deserialize_from(&mut reader).map_err(invalid_data)?;
+    let mut last_hash = start_hash;
+    for i in 0..num_entries {
+        let entry: Entry = bincode::deserialize_from(&mut reader).map_err(invalid_data)?;
+        if !entry.verify(&last_hash) {
+            warn!(target: LOG_TARGET, "entry {} in {:?} failed verification", i, path);
+            return Ok(false);
+        }
+        last_hash = entry.hash;
+    }
+    if !reader.is_empty() {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!(
+                "{} trailing bytes after {num_entries} entries",
+                reader.len()
+            ),
+        ));
+    }
+    Ok(true)
+}
+
+pub fn next_entry_mut(start: &mut Hash, num_hashes: u64, transactions: Vec<Transaction>) -> Entry {
+    let entry = Entry::new(start, num_hashes, transactions);
+    *start = entry.hash;
+    entry
+}
+
+pub fn create_ticks(num_ticks: u64, hashes_per_tick: u64, mut hash: Hash) -> Vec<Entry> {
+    repeat_with(|| next_entry_mut(&mut hash, hashes_per_tick, vec![]))
+        .take(num_ticks as usize)
+        .collect()
+}
+
+/// Same as `create_ticks`, but advances the caller's `poh_state` instead of starting from a
+/// hash, so successive calls continue one chain. `poh_state` must tick every
+/// `hashes_per_tick` hashes, or be in low power mode, and must not hold a partial tick.
+pub fn create_ticks_continuing(
+    poh_state: &mut Poh,
+    num_ticks: u64,
+    hashes_per_tick: u64,
+) -> Vec<Entry> {
+    assert!(hashes_per_tick > 0);
+    repeat_with(|| {
+        poh_state.hash(hashes_per_tick - 1);
+        let tick = poh_state
+            .tick()
+            .expect("poh_state ticks every hashes_per_tick hashes");
+        assert_eq!(tick.num_hashes, hashes_per_tick);
+        Entry {
+            num_hashes: tick.num_hashes,
+            hash: tick.hash,
+            transactions: vec![],
+        }
+    })
+    .take(num_ticks as usize)
+    .collect()
+}
+
+/// Same as `create_ticks`, but also verifies the generated ticks against `hash` as a
+/// self-check.
+pub fn create_verified_ticks(
+    num_ticks: u64,
+    hashes_per_tick: u64,
+    hash: Hash,
+) -> (Vec<Entry>, bool) {
+    let ticks = create_ticks(num_ticks, hashes_per_tick, hash);
+    let verified = ticks.verify(&hash);
+    (ticks, verified)
+}
+
+/// Creates one transaction entry per batch in `tx_batches`, starting from `start`, with a
+/// tick of `hashes_per_tick` hashes inserted after every `ticks_every` batches.
+pub fn create_entries_with_ticks(
+    start: Hash,
+    tx_batches: Vec<Vec<Transaction>>,
+    hashes_per_tick: u64,
+    ticks_every: usize,
+) -> Vec<Entry> {
+    let mut hash = start;
+    let mut entries = Vec::with_capacity(
+        tx_batches.len() + tx_batches.len().checked_div(ticks_every).unwrap_or(0),
+    );
+    for (i, transactions) in tx_batches.into_iter().enumerate() {
+        entries.push(next_entry_mut(&mut hash, 1, transactions));
+        if ticks_every > 0 && (i + 1) % ticks_every == 0 {
+            entries.push(next_entry_mut(&mut hash, hashes_per_tick, vec![]));
+        }
+    }
+    entries
+}
+
+pub fn create_random_ticks(num_ticks: u64, max_hashes_per_tick: u64, mut hash: Hash) -> Vec<Entry> {
+    repeat_with(|| {
+        let hashes_per_tick = thread_rng().gen_range(1, max_hashes_per_tick);
+        next_entry_mut(&mut hash, hashes_per_tick, vec![])
+    })
+    .take(num_ticks as usize)
+    .collect()
+}
+
+/// Corrupts one field of a randomly chosen entry so that the slice no longer verifies, and
+/// returns the index of that entry. Panics if `entries` is empty.
+#[cfg(feature = "dev-context-only-utils")]
+pub fn corrupt_random_entry(entries: &mut [Entry], rng: &mut impl Rng) -> usize {
+    use solana_sdk::signature::Signature;
+    assert!(!entries.is_empty(), "no entries to corrupt");
+    let index = rng.gen_range(0, entries.len());
+    let entry = &mut entries[index];
+    let num_choices = if entry.transactions.is_empty() { 2 } else { 3 };
+    match rng.gen_range(0, num_choices) {
+        0 => {
+            let mut hash = entry.hash;
+            while hash == entry.hash {
+                hash = Hash::new_from_array(rng.gen());
+            }
+            entry.hash = hash;
+        }
+        1 => entry.num_hashes = entry.num_hashes.wrapping_add(1),
+        _ => {
+            let tx_index = rng.gen_range(0, entry.transactions.len());
+            let tx = &mut entry.transactions[tx_index];
+            let mut bytes = [0u8; 64];
+            rng.fill(&mut bytes[..]);
+            match tx.signatures.first_mut() {
+                Some(signature) => *signature = Signature::from(bytes),
+                None => tx.signatures.push(Signature::from(bytes)),
+            }
+        }
+    }
+    index
+}
+
+/// Same as `create_ticks`, but with entry `fail_index` corrupted so that the slice verifies
+/// only up to it. The corrupt tick keeps its hash, so the entries after it still link to it.
+/// Panics if `fail_index` is out of range.
+#[cfg(feature = "dev-context-only-utils")]
+pub fn create_ticks_with_failure_at(
+    num_ticks: u64,
+    hashes_per_tick: u64,
+    hash: Hash,
+    fail_index: usize,
+) -> Vec<Entry> {
+    let mut ticks = create_ticks(num_ticks, hashes_per_tick, hash);
+    let entry = &mut ticks[fail_index];
+    entry.num_hashes = entry.num_hashes.wrapping_add(1);
+    ticks
+}
+
+/// Assembles an entry from serialized transactions, e.g. as received in packets. The
+/// `hash` is taken as given and not recomputed, so the result still needs to be verified.
+pub fn entry_from_parts(
+    num_hashes: u64,
+    hash: Hash,
+    tx_bytes: &[Vec<u8>],
+) -> bincode::Result<Entry> {
+    let transactions = tx_bytes
+        .iter()
+        .map(|bytes| bincode::deserialize(bytes))
+        .collect::<bincode::Result<Vec<VersionedTransaction>>>()?;
+    Ok(Entry {
+        num_hashes,
+        hash,
+        transactions,
+    })
+}
+
+/// Creates the next Tick or Transaction Entry `num_hashes` after `start_hash`.
+pub fn next_entry(prev_hash: &Hash, num_hashes: u64, transactions: Vec<Transaction>) -> Entry {
+    let transactions = transactions.into_iter().map(Into::into).collect::<Vec<_>>();
+    next_versioned_entry(prev_hash, num_hashes, transactions)
+}
+
+/// Creates the next Tick or Transaction Entry `num_hashes` after `start_hash`.
+pub fn next_versioned_entry(
+    prev_hash: &Hash,
+    num_hashes: u64,
+    transactions: Vec<VersionedTransaction>,
+) -> Entry {
+    assert!(num_hashes > 0 || transactions.is_empty());
+    Entry {
+        num_hashes,
+        hash: next_hash(prev_hash, num_hashes, &transactions),
+        transactions,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        solana_perf::test_tx::{test_invalid_tx, test_tx},
+        solana_sdk::{
+            hash::{hash, Hash},
+            pubkey::Pubkey,
+            signature::{Keypair, Signer},
+            system_transaction,
+            transaction::{
+                Result, SanitizedTransaction, SimpleAddressLoader, VersionedTransaction,
+            },
+        },
+    };
+
+    #[test]
+    fn test_entry_verify() {
+        let zero = Hash::default();
+        let one = hash(zero.as_ref());
+        assert!(Entry::new_tick(0, &zero).verify(&zero)); // base case, never used
+        assert!(!Entry::new_tick(0, &zero).verify(&one)); // base case, bad
+        assert!(next_entry(&zero, 1, vec![]).verify(&zero)); // inductive step
+        assert!(!next_entry(&zero, 1, vec![]).verify(&one)); // inductive step, bad
+    }
+
+    fn test_verify_transactions(
+        entries: Vec<Entry>,
+        skip_verification: bool,
+        verify_recyclers: VerifyRecyclers,
+        verify: Arc<
+            dyn Fn(
+                    VersionedTransaction,
+                    TransactionVerificationMode,
+                ) -> Result<SanitizedTransaction>
+                + Send
+                + Sync,
+        >,
+    ) -> bool {
+        let verify_func = {
+            let verify = verify.clone();
+            let verification_mode = if skip_verification {
+                TransactionVerificationMode::HashOnly
+            } else {
+                TransactionVerificationMode::FullVerification
+            };
+            move |versioned_tx: VersionedTransaction| -> Result<SanitizedTransaction> {
+                verify(versioned_tx, verification_mode)
+            }
+        };
+
+        let cpu_verify_result = verify_transactions(entries.clone(), Arc::new(verify_func));
+        let mut gpu_verify_result: EntrySigVerificationState = {
+            let verify_result = start_verify_transactions(
+                entries,
+                skip_verification,
+                false,
+                false,
+                verify_recyclers,
+                verify,
+            );
+            match verify_result {
+                Ok(res) => res,
+                _ => EntrySigVerificationState {
+                    verification_status: EntryVerificationStatus::Failure,
+                    entries: None,
+                    device_verification_data: DeviceSigVerificationData::Cpu(),
+                    gpu_verify_duration_us: 0,
+                },
+            }
+        };
+
+        match cpu_verify_result {
+            Ok(_) => {
+                assert!(gpu_verify_result.verification_status != EntryVerificationStatus::Failure);
+                assert!(gpu_verify_result.finish_verify());
+                true
+            }
+            _ => {
+                assert!(
+                    gpu_verify_result.verification_status == EntryVerificationStatus::Failure
+                        || !gpu_verify_result.finish_verify()
+                );
+                false
+            }
+        }
+    }
+
+    #[test]
+    fn test_entry_gpu_verify() {
+        let verify_transaction = {
+            move |versioned_tx: VersionedTransaction,
+                  verification_mode: TransactionVerificationMode|
+                  -> Result<SanitizedTransaction> {
+                let sanitized_tx = {
+                    let message_hash =
+                        if verification_mode == TransactionVerificationMode::FullVerification {
+                            versioned_tx.verify_and_hash_message()?
+                        } else {
+                            versioned_tx.message.hash()
+                        };
+
+                    SanitizedTransaction::try_create(
+                        versioned_tx,
+                        message_hash,
+                        None,
+                        SimpleAddressLoader::Disabled,
+                    )
+                }?;
+
+                Ok(sanitized_tx)
+            }
+        };
+
+        let recycler = VerifyRecyclers::default();
+
+        // Make sure we test with a number of transactions that's not a multiple of PACKETS_PER_BATCH
+        let entries_invalid = (0..1025)
+            .map(|_| {
+                let transaction = test_invalid_tx();
+                next_entry_mut(&mut Hash::default(), 0, vec![transaction])
+            })
+            .collect::<Vec<_>>();
+
+        let entries_valid = (0..1025)
+            .map(|_| {
+                let transaction = test_tx();
+                next_entry_mut(&mut Hash::default(), 0, vec![transaction])
+            })
+            .collect::<Vec<_>>();
+
+        assert!(!test_verify_transactions(
+            entries_invalid,
+            false,
+            recycler.clone(),
+            Arc::new(verify_transaction)
+        ));
+        assert!(test_verify_transactions(
+            entries_valid,
+            false,
+            recycler,
+            Arc::new(verify_transaction)
+        ));
+    }
+
+    #[test]
+    fn test_transaction_reorder_attack() {
+        let zero = Hash::default();
 
         // First, verify entries
         let keypair = Keypair::new();
         let tx0 = system_transaction::transfer(&keypair, &keypair.pubkey(), 0, zero);
         let tx1 = system_transaction::transfer(&keypair, &keypair.pubkey(), 1, zero);
-        let mut e0 = Entry::new(&zero, 0, vec![tx0.clone(), tx1.clone()]);
-        assert!(e0.verify(&zero));
+        let mut e0 = Entry::new(&zero, 0, vec![tx0.clone(), tx1.clone()]);
+        assert!(e0.verify(&zero));
+
+        // Next, swap two transactions and ensure verification fails.
+        e0.transactions[0] = tx1.into(); // <-- attack
+        e0.transactions[1] = tx0.into();
+        assert!(!e0.verify(&zero));
+    }
+
+    #[test]
+    fn test_transaction_signing() {
+        use solana_sdk::signature::Signature;
+        let zero = Hash::default();
+
+        let keypair = Keypair::new();
+        let tx0 = system_transaction::transfer(&keypair, &keypair.pubkey(), 0, zero);
+        let tx1 = system_transaction::transfer(&keypair, &keypair.pubkey(), 1, zero);
+
+        // Verify entry with 2 transactions
+        let mut e0 = vec![Entry::new(&zero, 0, vec![tx0, tx1])];
+        assert!(e0.verify(&zero));
+
+        // Clear signature of the first transaction, see that it does not verify
+        let orig_sig = e0[0].transactions[0].signatures[0];
+        e0[0].transactions[0].signatures[0] = Signature::default();
+        assert!(!e0.verify(&zero));
+
+        // restore original signature
+        e0[0].transactions[0].signatures[0] = orig_sig;
+        assert!(e0.verify(&zero));
+
+        // Resize signatures and see verification fails.
+        let len = e0[0].transactions[0].signatures.len();
+        e0[0].transactions[0]
+            .signatures
+            .resize(len - 1, Signature::default());
+        assert!(!e0.verify(&zero));
+
+        // Pass an entry with no transactions
+        let e0 = vec![Entry::new(&zero, 0, vec![])];
+        assert!(e0.verify(&zero));
+    }
+
+    #[test]
+    fn test_next_entry() {
+        let zero = Hash::default();
+        let tick = next_entry(&zero, 1, vec![]);
+        assert_eq!(tick.num_hashes, 1);
+        assert_ne!(tick.hash, zero);
+
+        let tick = next_entry(&zero, 0, vec![]);
+        assert_eq!(tick.num_hashes, 0);
+        assert_eq!(tick.hash, zero);
+
+        let keypair = Keypair::new();
+        let tx0 = system_transaction::transfer(&keypair, &Pubkey::new_unique(), 42, zero);
+        let entry0 = next_entry(&zero, 1, vec![tx0.clone()]);
+        assert_eq!(entry0.num_hashes, 1);
+        assert_eq!(entry0.hash, next_hash(&zero, 1, &[tx0.into()]));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_next_entry_panic() {
+        let zero = Hash::default();
+        let keypair = Keypair::new();
+        let tx = system_transaction::transfer(&keypair, &keypair.pubkey(), 0, zero);
+        next_entry(&zero, 0, vec![tx]);
+    }
+
+    #[test]
+    fn test_verify_slice1() {
+        solana_logger::setup();
+        let zero = Hash::default();
+        let one = hash(zero.as_ref());
+        assert!(vec![][..].verify(&zero)); // base case
+        assert!(vec![Entry::new_tick(0, &zero)][..].verify(&zero)); // singleton case 1
+        assert!(!vec![Entry::new_tick(0, &zero)][..].verify(&one)); // singleton case 2, bad
+        assert!(vec![next_entry(&zero, 0, vec![]); 2][..].verify(&zero)); // inductive step
+
+        let mut bad_ticks = vec![next_entry(&zero, 0, vec![]); 2];
+        bad_ticks[1].hash = one;
+        assert!(!bad_ticks.verify(&zero)); // inductive step, bad
+    }
+
+    #[test]
+    fn test_verify_slice_with_hashes1() {
+        solana_logger::setup();
+        let zero = Hash::default();
+        let one = hash(zero.as_ref());
+        let two = hash(one.as_ref());
+        assert!(vec![][..].verify(&one)); // base case
+        assert!(vec![Entry::new_tick(1, &two)][..].verify(&one)); // singleton case 1
+        assert!(!vec![Entry::new_tick(1, &two)][..].verify(&two)); // singleton case 2, bad
+
+        let mut ticks = vec![next_entry(&one, 1, vec![])];
+        ticks.push(next_entry(&ticks.last().unwrap().hash, 1, vec![]));
+        assert!(ticks.verify(&one)); // inductive step
+
+        let mut bad_ticks = vec![next_entry(&one, 1, vec![])];
+        bad_ticks.push(next_entry(&bad_ticks.last().unwrap().hash, 1, vec![]));
+        bad_ticks[1].hash = one;
+        assert!(!bad_ticks.verify(&one)); // inductive step, bad
+    }
+
+    #[test]
+    fn test_verify_slice_with_hashes_and_transactions() {
+        solana_logger::setup();
+        let zero = Hash::default();
+        let one = hash(zero.as_ref());
+        let two = hash(one.as_ref());
+        let alice_keypair = Keypair::new();
+        let bob_keypair = Keypair::new();
+        let tx0 = system_transaction::transfer(&alice_keypair, &bob_keypair.pubkey(), 1, one);
+        let tx1 = system_transaction::transfer(&bob_keypair, &alice_keypair.pubkey(), 1, one);
+        assert!(vec![][..].verify(&one)); // base case
+        assert!(vec![next_entry(&one, 1, vec![tx0.clone()])][..].verify(&one)); // singleton case 1
+        assert!(!vec![next_entry(&one, 1, vec![tx0.clone()])][..].verify(&two)); // singleton case 2, bad
+
+        let mut ticks = vec![next_entry(&one, 1, vec![tx0.clone()])];
+        ticks.push(next_entry(
+            &ticks.last().unwrap().hash,
+            1,
+            vec![tx1.clone()],
+        ));
+        assert!(ticks.verify(&one)); // inductive step
+
+        let mut bad_ticks = vec![next_entry(&one, 1, vec![tx0])];
+        bad_ticks.push(next_entry(&bad_ticks.last().unwrap().hash, 1, vec![tx1]));
+        bad_ticks[1].hash = one;
+        assert!(!bad_ticks.verify(&one)); // inductive step, bad
+    }
+
+    #[test]
+    fn test_verify_tick_hash_count() {
+        let hashes_per_tick = 10;
+        let tx = VersionedTransaction::default();
+
+        let no_hash_tx_entry = Entry {
+            transactions: vec![tx.clone()],
+            ..Entry::default()
+        };
+        let single_hash_tx_entry = Entry {
+            transactions: vec![tx.clone()],
+            num_hashes: 1,
+            ..Entry::default()
+        };
+        let partial_tx_entry = Entry {
+            num_hashes: hashes_per_tick - 1,
+            transactions: vec![tx.clone()],
+            ..Entry::default()
+        };
+        let full_tx_entry = Entry {
+            num_hashes: hashes_per_tick,
+            transactions: vec![tx.clone()],
+            ..Entry::default()
+        };
+        let max_hash_tx_entry = Entry {
+            transactions: vec![tx],
+            num_hashes: u64::MAX,
+            ..Entry::default()
+        };
+
+        let no_hash_tick_entry = Entry::new_tick(0, &Hash::default());
+        let single_hash_tick_entry = Entry::new_tick(1, &Hash::default());
+        let partial_tick_entry = Entry::new_tick(hashes_per_tick - 1, &Hash::default());
+        let full_tick_entry = Entry::new_tick(hashes_per_tick, &Hash::default());
+        let max_hash_tick_entry = Entry::new_tick(u64::MAX, &Hash::default());
+
+        // empty batch should succeed if hashes_per_tick hasn't been reached
+        let mut tick_hash_count = 0;
+        let mut entries = vec![];
+        assert!(entries.verify_tick_hash_count(&mut tick_hash_count, hashes_per_tick));
+        assert_eq!(tick_hash_count, 0);
+
+        // empty batch should fail if hashes_per_tick has been reached
+        tick_hash_count = hashes_per_tick;
+        assert!(!entries.verify_tick_hash_count(&mut tick_hash_count, hashes_per_tick));
+        assert_eq!(tick_hash_count, hashes_per_tick);
+        tick_hash_count = 0;
+
+        // validation is disabled when hashes_per_tick == 0
+        entries = vec![max_hash_tx_entry.clone()];
+        assert!(entries.verify_tick_hash_count(&mut tick_hash_count, 0));
+        assert_eq!(tick_hash_count, 0);
+
+        // partial tick should fail
+        entries = vec![partial_tick_entry.clone()];
+        assert!(!entries.verify_tick_hash_count(&mut tick_hash_count, hashes_per_tick));
+        assert_eq!(tick_hash_count, hashes_per_tick - 1);
+        tick_hash_count = 0;
+
+        // full tick entry should succeed
+        entries = vec![no_hash_tx_entry, full_tick_entry.clone()];
+        assert!(entries.verify_tick_hash_count(&mut tick_hash_count, hashes_per_tick));
+        assert_eq!(tick_hash_count, 0);
+
+        // oversized tick entry should fail
+        assert!(!entries.verify_tick_hash_count(&mut tick_hash_count, hashes_per_tick - 1));
+        assert_eq!(tick_hash_count, hashes_per_tick);
+        tick_hash_count = 0;
+
+        // partial tx entry without tick entry should succeed
+        entries = vec![partial_tx_entry];
+        assert!(entries.verify_tick_hash_count(&mut tick_hash_count, hashes_per_tick));
+        assert_eq!(tick_hash_count, hashes_per_tick - 1);
+        tick_hash_count = 0;
+
+        // full tx entry with tick entry should succeed
+        entries = vec![full_tx_entry.clone(), no_hash_tick_entry];
+        assert!(entries.verify_tick_hash_count(&mut tick_hash_count, hashes_per_tick));
+        assert_eq!(tick_hash_count, 0);
+
+        // full tx entry with oversized tick entry should fail
+        entries = vec![full_tx_entry.clone(), single_hash_tick_entry.clone()];
+        assert!(!entries.verify_tick_hash_count(&mut tick_hash_count, hashes_per_tick));
+        assert_eq!(tick_hash_count, hashes_per_tick + 1);
+        tick_hash_count = 0;
+
+        // full tx entry without tick entry should fail
+        entries = vec![full_tx_entry];
+        assert!(!entries.verify_tick_hash_count(&mut tick_hash_count, hashes_per_tick));
+        assert_eq!(tick_hash_count, hashes_per_tick);
+        tick_hash_count = 0;
+
+        // tx entry and a tick should succeed
+        entries = vec![single_hash_tx_entry.clone(), partial_tick_entry];
+        assert!(entries.verify_tick_hash_count(&mut tick_hash_count, hashes_per_tick));
+        assert_eq!(tick_hash_count, 0);
+
+        // many tx entries and a tick should succeed
+        let tx_entries: Vec<Entry> = (0..hashes_per_tick - 1)
+            .map(|_| single_hash_tx_entry.clone())
+            .collect();
+        entries = [tx_entries, vec![single_hash_tick_entry]].concat();
+        assert!(entries.verify_tick_hash_count(&mut tick_hash_count, hashes_per_tick));
+        assert_eq!(tick_hash_count, 0);
+
+        // check overflow saturation should fail
+        entries = vec![full_tick_entry.clone(), max_hash_tick_entry];
+        assert!(!entries.verify_tick_hash_count(&mut tick_hash_count, hashes_per_tick));
+        assert_eq!(tick_hash_count, u64::MAX);
+        tick_hash_count = 0;
+
+        // check overflow saturation should fail
+        entries = vec![max_hash_tx_entry, full_tick_entry];
+        assert!(!entries.verify_tick_hash_count(&mut tick_hash_count, hashes_per_tick));
+        assert_eq!(tick_hash_count, u64::MAX);
+    }
+
+    #[test]
+    fn test_poh_verify_fuzz() {
+        solana_logger::setup();
+        for _ in 0..100 {
+            let mut time = Measure::start("ticks");
+            let num_ticks = thread_rng().gen_range(1, 100);
+            info!("create {} ticks:", num_ticks);
+            let mut entries = create_random_ticks(num_ticks, 100, Hash::default());
+            time.stop();
+
+            let mut modified = false;
+            if thread_rng().gen_ratio(1, 2) {
+                modified = true;
+                let modify_idx = thread_rng().gen_range(0, num_ticks) as usize;
+                entries[modify_idx].hash = hash(&[1, 2, 3]);
+            }
+
+            info!("done.. {}", time);
+            let mut time = Measure::start("poh");
+            let res = entries.verify(&Hash::default());
+            assert_eq!(res, !modified);
+            time.stop();
+            info!("{} {}", time, res);
+        }
+    }
+
+    #[test]
+    fn test_verify_time_monotonic() {
+        let zero = Hash::default();
+        assert!(Vec::<Entry>::new().verify_time_monotonic());
+
+        let mut entries = vec![Entry::new_tick(0, &zero)];
+        entries.extend(create_ticks(4, 2, zero));
+        assert!(entries.verify_time_monotonic());
+
+        entries.insert(2, Entry::new_tick(0, &entries[1].hash));
+        assert!(!entries.verify_time_monotonic());
+    }
+
+    #[test]
+    fn test_verify_thread_count() {
+        assert_eq!(verify_thread_count(), get_max_thread_count());
+    }
+
+    #[test]
+    fn test_verify_filter() {
+        let zero = Hash::default();
+        let (sender, receiver) = unbounded();
+        let verified = verify_filter(receiver, zero);
+
+        let good = create_ticks(4, 2, zero);
+        let mut bad = create_ticks(4, 2, good.last().unwrap().hash);
+        bad[1].hash = hash(&[1, 2, 3]);
+        sender.send(good.clone()).unwrap();
+        sender.send(bad).unwrap();
+        drop(sender);
+
+        assert_eq!(verified.iter().collect::<Vec<_>>(), vec![good]);
+    }
+
+    #[test]
+    fn test_find_duplicate_transactions() {
+        let zero = Hash::default();
+        let keypair = Keypair::new();
+        let tx0 = system_transaction::transfer(&keypair, &keypair.pubkey(), 0, zero);
+        let tx1 = system_transaction::transfer(&keypair, &keypair.pubkey(), 1, zero);
+        let mut hash = zero;
+        let mut entries = vec![
+            next_entry_mut(&mut hash, 1, vec![tx0.clone()]),
+            next_entry_mut(&mut hash, 1, vec![tx1]),
+            next_entry_mut(&mut hash, 1, vec![]),
+        ];
+        assert!(entries.find_duplicate_transactions().is_empty());
+
+        entries.push(next_entry_mut(&mut hash, 1, vec![tx0]));
+        assert_eq!(entries.find_duplicate_transactions(), vec![(0, 3)]);
+    }
+
+    #[test]
+    fn test_verify_timed() {
+        let zero = Hash::default();
+        let entries = create_ticks(16, 10_000, zero);
+        let (res, poh_duration_us, transaction_duration_us) = entries.verify_timed(&zero);
+        assert!(res);
+        assert!(poh_duration_us > 0);
+        assert!(transaction_duration_us <= poh_duration_us);
+
+        let (res, _, _) = entries.verify_timed(&Hash::new_unique());
+        assert!(!res);
+    }
+
+    #[test]
+    fn test_verify_progress() {
+        let zero = Hash::default();
+        assert!(Vec::<Entry>::new().verify_progress(&zero));
+
+        let degenerate = vec![Entry::new_tick(0, &zero); 3];
+        assert!(degenerate.verify(&zero));
+        assert!(!degenerate.verify_progress(&zero));
+
+        let entries = create_ticks(3, 1, zero);
+        assert!(entries.verify_progress(&zero));
+    }
+
+    #[test]
+    fn test_slice_signature_root() {
+        use solana_sdk::signature::Signature;
+        let zero = Hash::default();
+        let keypair = Keypair::new();
+        let tx0 = system_transaction::transfer(&keypair, &keypair.pubkey(), 0, zero);
+        let tx1 = system_transaction::transfer(&keypair, &keypair.pubkey(), 1, zero);
+        let mut hash = zero;
+        let mut entries = vec![
+            next_entry_mut(&mut hash, 1, vec![tx0.clone()]),
+            next_entry_mut(&mut hash, 1, vec![]),
+            next_entry_mut(&mut hash, 1, vec![tx1.clone()]),
+        ];
+        let root = entries.slice_signature_root();
+        assert_ne!(root, Hash::default());
+        assert_eq!(root, entries.slice_signature_root());
+        assert_eq!(root, hash_transactions(&[tx0.into(), tx1.into()]));
+
+        entries[2].transactions[0].signatures[0] = Signature::default();
+        assert_ne!(root, entries.slice_signature_root());
+
+        assert_eq!(Vec::<Entry>::new().slice_signature_root(), Hash::default());
+    }
+
+    #[test]
+    fn test_verify_transaction_sanity() {
+        let zero = Hash::default();
+        let keypair = Keypair::new();
+        let tx0 = system_transaction::transfer(&keypair, &keypair.pubkey(), 0, zero);
+        let mut tx1 = system_transaction::transfer(&keypair, &keypair.pubkey(), 1, zero);
+        let mut hash = zero;
+        let mut entries = vec![
+            next_entry_mut(&mut hash, 1, vec![]),
+            next_entry_mut(&mut hash, 1, vec![tx0.clone()]),
+        ];
+        assert_eq!(entries.verify_transaction_sanity(), Ok(()));
+
+        tx1.message.instructions[0].program_id_index = u8::MAX;
+        entries.push(next_entry_mut(&mut hash, 1, vec![tx0, tx1]));
+        assert_eq!(entries.verify_transaction_sanity(), Err((2, 1)));
+    }
+
+    #[test]
+    fn test_create_entries_with_ticks() {
+        let zero = Hash::default();
+        let keypair = Keypair::new();
+        let tx_batches: Vec<_> = (0..5)
+            .map(|lamports| {
+                vec![system_transaction::transfer(
+                    &keypair,
+                    &keypair.pubkey(),
+                    lamports,
+                    zero,
+                )]
+            })
+            .collect();
+
+        let entries = create_entries_with_ticks(zero, tx_batches.clone(), 4, 2);
+        assert_eq!(entries.len(), 7);
+        let tick_positions: Vec<_> = entries
+            .iter()
+            .enumerate()
+            .filter_map(|(i, entry)| entry.is_tick().then_some(i))
+            .collect();
+        assert_eq!(tick_positions, vec![2, 5]);
+        assert!(entries
+            .iter()
+            .filter(|e| e.is_tick())
+            .all(|e| e.num_hashes == 4));
+        assert!(entries.verify(&zero));
+
+        let entries = create_entries_with_ticks(zero, tx_batches, 4, 0);
+        assert_eq!(entries.tick_count(), 0);
+        assert!(entries.verify(&zero));
+    }
+
+    #[test]
+    fn test_verify_mmap() {
+        use std::io::Write;
+        let zero = Hash::default();
+        let entries = create_ticks(8, 4, zero);
+        let bytes = bincode::serialize(&entries).unwrap();
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("entries");
+
+        File::create(&path).unwrap().write_all(&bytes).unwrap();
+        assert!(verify_mmap(&path, zero).unwrap());
+        assert!(!verify_mmap(&path, Hash::new_unique()).unwrap());
+
+        // truncated tail
+        File::create(&path)
+            .unwrap()
+            .write_all(&bytes[..bytes.len() - 1])
+            .unwrap();
+        assert_eq!(
+            verify_mmap(&path, zero).unwrap_err().kind(),
+            io::ErrorKind::InvalidData
+        );
+
+        // trailing garbage
+        File::create(&path)
+            .unwrap()
+            .write_all(&[bytes.as_slice(), &[0u8; 3]].concat())
+            .unwrap();
+        assert_eq!(
+            verify_mmap(&path, zero).unwrap_err().kind(),
+            io::ErrorKind::InvalidData
+        );
+    }
+
+    #[test]
+    fn test_verify_benchmark() {
+        let zero = Hash::default();
+        let entries = create_ticks(64, 1_000, zero);
+        let benchmark = entries.verify_benchmark(&zero);
+        assert!(benchmark.generic_us > 0);
+        assert!(benchmark.agreed);
+        if api().is_none() {
+            assert_eq!(benchmark.avx2_us, None);
+            assert_eq!(benchmark.avx512_us, None);
+        }
+
+        let benchmark = entries.verify_benchmark(&Hash::new_unique());
+        assert!(benchmark.agreed);
+    }
+
+    #[test]
+    fn test_hash_transactions_zero_signatures() {
+        let zero = Hash::default();
+        let keypair = Keypair::new();
+        let tx0: VersionedTransaction =
+            system_transaction::transfer(&keypair, &keypair.pubkey(), 0, zero).into();
+        let tx1: VersionedTransaction =
+            system_transaction::transfer(&keypair, &keypair.pubkey(), 1, zero).into();
+
+        // signed transactions hash only their signatures
+        let signatures: Vec<_> = [&tx0, &tx1]
+            .iter()
+            .flat_map(|tx| tx.signatures.iter())
+            .collect();
+        assert_eq!(
+            hash_transactions(&[tx0.clone(), tx1.clone()]),
+            *MerkleTree::new(&signatures).get_root().unwrap()
+        );
+
+        // transactions without signatures contribute nothing to the root, which is consensus
+        // behavior until a feature gate changes it
+        let mut unsigned0 = tx0.clone();
+        let mut unsigned1 = tx1;
+        unsigned0.signatures.clear();
+        unsigned1.signatures.clear();
+        assert_eq!(
+            hash_transactions(&[unsigned0.clone()]),
+            hash_transactions(&[unsigned1])
+        );
+        assert_eq!(hash_transactions(&[unsigned0.clone()]), Hash::default());
+        assert_eq!(
+            hash_transactions(&[unsigned0, tx0.clone()]),
+            hash_transactions(&[tx0])
+        );
+    }
+
+    #[test]
+    fn test_verify_bounded() {
+        let zero = Hash::default();
+        let keypair = Keypair::new();
+        let tx = system_transaction::transfer(&keypair, &keypair.pubkey(), 0, zero);
+        let mut entries = create_ticks(4, 10, zero);
+        entries.push(next_entry(&entries[3].hash, 2, vec![tx.clone(), tx]));
+        assert_eq!(entries.verify_cost_estimate(), (42, 2));
+
+        assert_eq!(entries.verify_bounded(&zero, 42, 2), Ok(true));
+        assert_eq!(
+            entries.verify_bounded(&Hash::new_unique(), 42, 2),
+            Ok(false)
+        );
+        assert_eq!(
+            entries.verify_bounded(&zero, 41, 2),
+            Err(LimitExceeded {
+                num_hashes: 42,
+                num_signatures: 2,
+            })
+        );
+        assert!(entries.verify_bounded(&zero, 42, 1).is_err());
+    }
+
+    #[test]
+    fn test_nth_tick_index() {
+        let zero = Hash::default();
+        let keypair = Keypair::new();
+        let tx = system_transaction::transfer(&keypair, &keypair.pubkey(), 0, zero);
+        let entries = create_entries_with_ticks(zero, vec![vec![tx]; 6], 4, 3);
+        assert_eq!(entries.nth_tick_index(0), Some(3));
+        assert_eq!(entries.nth_tick_index(1), Some(7));
+        assert_eq!(entries.nth_tick_index(2), None);
+        assert_eq!(entries.nth_tick_index(u64::MAX), None);
+        assert_eq!(Vec::<Entry>::new().nth_tick_index(0), None);
+    }
+
+    #[test]
+    fn test_verify_with_verifier() {
+        use solana_sdk::signature::Signature;
+
+        struct AcceptAll;
+        impl SignatureVerifier for AcceptAll {
+            fn verify(&self, _tx: &VersionedTransaction) -> bool {
+                true
+            }
+        }
+
+        let zero = Hash::default();
+        let keypair = Keypair::new();
+        let tx = system_transaction::transfer(&keypair, &keypair.pubkey(), 0, zero);
+        let entries = vec![next_entry(&zero, 1, vec![tx.clone()])];
+        assert!(entries.verify_with_verifier(&zero, &Ed25519SignatureVerifier));
+        assert!(entries.verify_with_verifier(&zero, &AcceptAll));
+
+        let mut unsigned_tx = tx;
+        unsigned_tx.signatures = vec![Signature::default()];
+        let entries = vec![next_entry(&zero, 1, vec![unsigned_tx])];
+        assert!(!entries.verify_with_verifier(&zero, &Ed25519SignatureVerifier));
+        assert!(entries.verify_with_verifier(&zero, &AcceptAll));
+        assert!(!entries.verify_with_verifier(&Hash::new_unique(), &AcceptAll));
+    }
+
+    #[test]
+    fn test_create_verified_ticks() {
+        let zero = Hash::default();
+        let (ticks, verified) = create_verified_ticks(8, 16, zero);
+        assert!(verified);
+        assert_eq!(ticks, create_ticks(8, 16, zero));
+
+        let (ticks, verified) = create_verified_ticks(0, 16, zero);
+        assert!(verified);
+        assert!(ticks.is_empty());
+    }
+
+    #[test]
+    fn test_start_verify_empty() {
+        let entries: Vec<Entry> = vec![];
+        let mut state = entries.start_verify(&Hash::new_unique(), VerifyRecyclers::default());
+        // Resolved up front without touching the GPU path or its recyclers
+        assert_eq!(state.status(), EntryVerificationStatus::Success);
+        assert!(matches!(
+            state.device_verification_data,
+            DeviceVerificationData::Cpu()
+        ));
+        assert_eq!(state.poh_duration_us(), 0);
+        assert_eq!(state.transaction_duration_us(), 0);
+        assert!(state.finish_verify());
+        assert_eq!(state.poh_duration_us(), 0);
+    }
+
+    #[test]
+    fn test_verify_with_chain() {
+        let zero = Hash::default();
+        let mut entries = create_ticks(5, 3, zero);
+        let (res, chain) = entries.verify_with_chain(&zero);
+        assert!(res);
+        assert_eq!(
+            chain,
+            entries.iter().map(|entry| entry.hash).collect::<Vec<_>>()
+        );
+
+        entries[2].hash = Hash::new_unique();
+        let (res, chain) = entries.verify_with_chain(&zero);
+        assert!(!res);
+        assert_ne!(chain[2], entries[2].hash);
+        assert_eq!(chain[1], entries[1].hash);
+    }
+
+    #[test]
+    fn test_hash_transactions_single_signature() {
+        let zero = Hash::default();
+        let keypair = Keypair::new();
+        let tx: VersionedTransaction =
+            system_transaction::transfer(&keypair, &keypair.pubkey(), 0, zero).into();
+        assert_eq!(tx.signatures.len(), 1);
+        assert_eq!(
+            hash_transactions(&[tx.clone()]),
+            *MerkleTree::new(&tx.signatures).get_root().unwrap()
+        );
+
+        let entry = next_versioned_entry(&zero, 1, vec![tx]);
+        assert!(entry.verify(&zero));
+        assert!(vec![entry].verify(&zero));
+    }
+
+    #[test]
+    fn test_verify_and_dump() {
+        use solana_sdk::signature::Signature;
+        let zero = Hash::default();
+        let keypair = Keypair::new();
+        let tx = system_transaction::transfer(&keypair, &keypair.pubkey(), 0, zero);
+        let mut hash = zero;
+        let mut entries = vec![
+            next_entry_mut(&mut hash, 1, vec![]),
+            next_entry_mut(&mut hash, 1, vec![tx.clone()]),
+            next_entry_mut(&mut hash, 1, vec![tx.clone(), tx]),
+        ];
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("dump.json");
+        assert!(entries.verify_and_dump(&zero, &path).unwrap());
+        assert!(!path.exists());
+
+        entries[2].transactions[1].signatures[0] = Signature::default();
+        assert!(!entries.verify_and_dump(&zero, &path).unwrap());
+        let dump: VerifyFailureDump = serde_json::from_reader(File::open(&path).unwrap()).unwrap();
+        assert_eq!(dump.first_failing_index, 2);
+        assert_eq!(dump.expected_hash, entries[2].hash.to_string());
+        assert_eq!(
+            dump.actual_hash,
+            next_hash(&entries[1].hash, 1, &entries[2].transactions).to_string()
+        );
+        assert_eq!(dump.failing_transactions, vec![1]);
+    }
+
+    #[test]
+    fn test_verify_with_tick_height() {
+        let zero = Hash::default();
+        let hashes_per_tick = 8;
+        let mut entries = create_ticks(2, hashes_per_tick, zero);
+        let mut hash = entries.last().unwrap().hash;
+        let keypair = Keypair::new();
+        let tx = system_transaction::transfer(&keypair, &keypair.pubkey(), 0, zero);
+        entries.push(next_entry_mut(&mut hash, 3, vec![tx]));
+        entries.push(next_entry_mut(&mut hash, 5, vec![]));
+        assert!(entries.verify(&zero));
+
+        let mut tick_height = 10;
+        assert!(entries.verify_with_tick_height(&zero, hashes_per_tick, &mut tick_height));
+        assert_eq!(tick_height, 13);
+
+        // ticks do not land on the boundaries implied by a different hashes_per_tick
+        let mut tick_height = 10;
+        assert!(!entries.verify_with_tick_height(&zero, 4, &mut tick_height));
+        let mut tick_height = 10;
+        assert!(!entries.verify_with_tick_height(&zero, 16, &mut tick_height));
+
+        // a trailing tick with too many hashes is rejected even though the chain is valid
+        entries.push(next_entry_mut(&mut hash, hashes_per_tick + 1, vec![]));
+        assert!(entries.verify(&zero));
+        let mut tick_height = 0;
+        assert!(!entries.verify_with_tick_height(&zero, hashes_per_tick, &mut tick_height));
+        assert_eq!(tick_height, 3);
+    }
+
+    #[test]
+    fn test_corrupt_random_entry() {
+        let zero = Hash::default();
+        let keypair = Keypair::new();
+        let tx = system_transaction::transfer(&keypair, &keypair.pubkey(), 0, zero);
+        let entries = create_entries_with_ticks(zero, vec![vec![tx]; 8], 4, 2);
+        let mut rng = thread_rng();
+        for _ in 0..32 {
+            let mut corrupted = entries.clone();
+            let index = corrupt_random_entry(&mut corrupted, &mut rng);
+            assert!(!corrupted.verify(&zero));
+            for (i, (original, corrupted)) in entries.iter().zip(&corrupted).enumerate() {
+                assert_eq!(i == index, original != corrupted);
+            }
+        }
+    }
+
+    #[test]
+    fn test_verify_into() {
+        let zero = Hash::default();
+        let mut hashes_pinned = PinnedVec::default();
+        let mut num_hashes_pinned = PinnedVec::default();
+        // Both the GPU (if available) and CPU paths
+        for (min_gpu_verify_entries, max_gpu_verify_entries) in [(0, usize::MAX), (0, 0)] {
+            let mut verify_into = |entries: &[Entry], start_hash: &Hash| {
+                entries.verify_into(
+                    start_hash,
+                    &mut hashes_pinned,
+                    &mut num_hashes_pinned,
+                    min_gpu_verify_entries,
+                    max_gpu_verify_entries,
+                )
+            };
+
+            let entries = create_ticks(64, 4, zero);
+            let mut state = verify_into(&entries, &zero);
+            assert_ne!(state.status(), EntryVerificationStatus::Pending);
+            assert!(state.finish_verify());
+
+            // reuse the same buffers for a shorter, corrupted slice
+            let mut entries = create_ticks(16, 4, entries.last().unwrap().hash);
+            let start_hash = entries[0].hash;
+            entries[8].hash = Hash::new_unique();
+            assert!(!verify_into(&entries[1..], &start_hash).finish_verify());
+            assert!(verify_into(&entries[1..8], &start_hash).finish_verify());
+        }
+    }
+
+    #[test]
+    fn test_verify_slot_tick_structure() {
+        let zero = Hash::default();
+        let hashes_per_tick = 4;
+        let ticks_per_slot = 8;
+        let keypair = Keypair::new();
+        let tx = system_transaction::transfer(&keypair, &keypair.pubkey(), 0, zero);
+
+        let ticks = create_ticks(ticks_per_slot, hashes_per_tick, zero);
+        assert!(ticks.verify_slot_tick_structure(hashes_per_tick, ticks_per_slot));
+
+        // a transaction entry sharing a tick's hashes
+        let mut hash = zero;
+        let mut entries = vec![
+            next_entry_mut(&mut hash, 1, vec![tx.clone()]),
+            next_entry_mut(&mut hash, hashes_per_tick - 1, vec![]),
+        ];
+        entries.extend(create_ticks(ticks_per_slot - 1, hashes_per_tick, hash));
+        assert!(entries.verify_slot_tick_structure(hashes_per_tick, ticks_per_slot));
+
+        // missing tick
+        assert!(!ticks[1..].verify_slot_tick_structure(hashes_per_tick, ticks_per_slot));
+
+        // trailing hashes after the last tick
+        let mut entries = ticks.clone();
+        entries.push(next_entry(&ticks.last().unwrap().hash, 1, vec![tx]));
+        assert!(!entries.verify_slot_tick_structure(hashes_per_tick, ticks_per_slot));
+
+        // wrong spacing
+        assert!(!ticks.verify_slot_tick_structure(hashes_per_tick + 1, ticks_per_slot));
+    }
+
+    #[test]
+    fn test_entry_summary() {
+        let zero = Hash::default();
+        let keypair = Keypair::new();
+        let tx = system_transaction::transfer(&keypair, &keypair.pubkey(), 0, zero);
+        let mut hash = zero;
+        let entries = vec![
+            next_entry_mut(&mut hash, 3, vec![]),
+            next_entry_mut(&mut hash, 1, vec![tx.clone(), tx]),
+        ];
+        assert_eq!(
+            entries[0].summary(),
+            EntrySummary {
+                num_hashes: 3,
+                hash: entries[0].hash,
+                num_transactions: 0,
+                is_tick: true,
+            }
+        );
+        assert_eq!(
+            entries[1].summary(),
+            EntrySummary {
+                num_hashes: 1,
+                hash: entries[1].hash,
+                num_transactions: 2,
+                is_tick: false,
+            }
+        );
+        assert_eq!(
+            entries.summaries(),
+            vec![entries[0].summary(), entries[1].summary()]
+        );
+    }
+
+    #[test]
+    fn test_start_verify_transactions_ed25519_precompile() {
+        use solana_sdk::ed25519_instruction::new_ed25519_instruction;
+
+        // Doesn't check precompiles itself, so only `ed25519_program_enabled` does
+        let verify = Arc::new(
+            |versioned_tx: VersionedTransaction,
+             verification_mode: TransactionVerificationMode|
+             -> Result<SanitizedTransaction> {
+                let message_hash =
+                    if verification_mode == TransactionVerificationMode::FullVerification {
+                        versioned_tx.verify_and_hash_message()?
+                    } else {
+                        versioned_tx.message.hash()
+                    };
+                SanitizedTransaction::try_create(
+                    versioned_tx,
+                    message_hash,
+                    None,
+                    SimpleAddressLoader::Disabled,
+                )
+            },
+        );
+        let start_verify = |tx: &Transaction, ed25519_program_enabled: bool| {
+            let entries = vec![next_entry(&Hash::default(), 1, vec![tx.clone()])];
+            start_verify_transactions(
+                entries,
+                false,
+                false,
+                ed25519_program_enabled,
+                VerifyRecyclers::default(),
+                verify.clone(),
+            )
+            .map(|mut state| state.finish_verify())
+        };
+
+        let zero = Hash::default();
+        let payer = Keypair::new();
+        let privkey = ed25519_dalek::Keypair::generate(&mut thread_rng());
+        let instruction = new_ed25519_instruction(&privkey, b"hello");
+        let valid_tx = Transaction::new_signed_with_payer(
+            &[instruction.clone()],
+            Some(&payer.pubkey()),
+            &[&payer],
+            zero,
+        );
+        let mut invalid_instruction = instruction;
+        *invalid_instruction.data.last_mut().unwrap() ^= 0xff;
+        let invalid_tx = Transaction::new_signed_with_payer(
+            &[invalid_instruction],
+            Some(&payer.pubkey()),
+            &[&payer],
+            zero,
+        );
+
+        assert_eq!(start_verify(&valid_tx, true), Ok(true));
+        assert_eq!(
+            start_verify(&invalid_tx, true),
+            Err(TransactionError::InvalidAccountIndex)
+        );
+        // Not checked while the program is disabled
+        assert_eq!(start_verify(&invalid_tx, false), Ok(true));
+
+        assert_eq!(
+            verify_precompile_instructions(&valid_tx.clone().into(), false, true),
+            Ok(true)
+        );
+        assert!(verify_precompile_instructions(&invalid_tx.clone().into(), false, true).is_err());
+        assert_eq!(
+            verify_precompile_instructions(&invalid_tx.into(), true, false),
+            Ok(true)
+        );
+        assert_eq!(
+            verify_precompile_instructions(&test_tx().into(), true, true),
+            Ok(false)
+        );
+    }
+
+    #[test]
+    fn test_verify_classified() {
+        let zero = Hash::default();
+        let entries = create_ticks(8, 4, zero);
+        assert_eq!(entries.verify_classified(&zero), Ok(()));
+        assert_eq!(
+            entries.verify_classified(&Hash::new_unique()),
+            Err(VerifyFailureKind::Permanent)
+        );
+
+        // Simulate the GPU verification thread failing
+        let mut state = EntryVerificationState {
+            verification_status: EntryVerificationStatus::Pending,
+            poh_duration_us: 0,
+            transaction_duration_us: 0,
+            gpu_wait_us: 0,
+            gpu_compute_us: 0,
+            entry_counts: EntryCounts::default(),
+            device_verification_data: DeviceVerificationData::Gpu(GpuVerificationData {
+                thread_h: Some(thread::spawn(|| -> (u64, u64) {
+                    panic!("GPU PoH verify many failed")
+                })),
+                hashes: Some(Arc::new(Mutex::new(PinnedVec::default()))),
+                verifications: Some(vec![]),
+            }),
+        };
+        assert_eq!(
+            state.finish_verify_classified(),
+            Err(VerifyFailureKind::Infrastructure)
+        );
+        assert_eq!(state.status(), EntryVerificationStatus::Failure);
+
+        // As `verify_into` reports a failed GPU
+        let mut state = EntryVerificationState {
+            verification_status: EntryVerificationStatus::Failure,
+            poh_duration_us: 0,
+            transaction_duration_us: 0,
+            gpu_wait_us: 0,
+            gpu_compute_us: 0,
+            entry_counts: EntryCounts::default(),
+            device_verification_data: DeviceVerificationData::DeviceFailed(),
+        };
+        assert_eq!(
+            state.finish_verify_classified(),
+            Err(VerifyFailureKind::Infrastructure)
+        );
+    }
+
+    #[test]
+    fn test_without_trailing_ticks() {
+        let zero = Hash::default();
+        let keypair = Keypair::new();
+        let tx = system_transaction::transfer(&keypair, &keypair.pubkey(), 0, zero);
+        let entries = create_entries_with_ticks(zero, vec![vec![tx]; 3], 4, 2);
+        assert!(!entries.last().unwrap().is_tick());
+        assert_eq!(entries.without_trailing_ticks(), &entries[..]);
+
+        let mut padded = entries.clone();
+        padded.extend(create_ticks(3, 4, entries.last().unwrap().hash));
+        assert_eq!(padded.without_trailing_ticks(), &entries[..]);
+
+        let ticks = create_ticks(3, 4, zero);
+        assert!(ticks.without_trailing_ticks().is_empty());
+        assert!(Vec::<Entry>::new().without_trailing_ticks().is_empty());
+    }
+
+    #[test]
+    fn test_verify_any_start() {
+        let start = Hash::new_unique();
+        let mut entries = create_ticks(8, 4, start);
+        let candidates = [Hash::new_unique(), start, Hash::new_unique()];
+        assert_eq!(entries.verify_any_start(&candidates), Some(1));
+        assert_eq!(entries.verify_any_start(&candidates[2..]), None);
+        assert_eq!(entries.verify_any_start(&[]), None);
+        assert_eq!(Vec::<Entry>::new().verify_any_start(&candidates), Some(0));
+
+        entries[5].hash = Hash::new_unique();
+        assert_eq!(entries.verify_any_start(&candidates), None);
+    }
+
+    #[test]
+    fn test_verify_collecting_accounts() {
+        let zero = Hash::default();
+        let alice = Keypair::new();
+        let bob = Pubkey::new_unique();
+        let carol = Pubkey::new_unique();
+        let tx0 = system_transaction::transfer(&alice, &bob, 1, zero);
+        let tx1 = system_transaction::transfer(&alice, &carol, 1, zero);
+        let entries = create_entries_with_ticks(zero, vec![vec![tx0], vec![tx1]], 4, 1);
+
+        let (res, accounts) = entries.verify_collecting_accounts(&zero);
+        assert!(res);
+        assert_eq!(
+            accounts,
+            HashSet::from([alice.pubkey(), bob, carol, solana_sdk::system_program::id()])
+        );
+
+        let (res, accounts_on_failure) = entries.verify_collecting_accounts(&Hash::new_unique());
+        assert!(!res);
+        assert_eq!(accounts_on_failure, accounts);
+    }
+
+    #[test]
+    fn test_start_verify_min_gpu_verify_entries() {
+        let zero = Hash::default();
+        let recyclers = VerifyRecyclers::default();
+        assert_eq!(
+            recyclers.min_gpu_verify_entries(),
+            DEFAULT_MIN_GPU_VERIFY_ENTRIES
+        );
+        let recyclers = recyclers.with_min_gpu_verify_entries(64);
+        assert_eq!(recyclers.min_gpu_verify_entries(), 64);
+
+        // Below the threshold the CPU path is taken, whether or not a GPU is available
+        let entries = create_ticks(63, 4, zero);
+        let mut state = entries.start_verify(&zero, recyclers);
+        assert!(matches!(
+            state.device_verification_data,
+            DeviceVerificationData::Cpu()
+        ));
+        assert_eq!(state.status(), EntryVerificationStatus::Success);
+        assert!(state.finish_verify());
+
+        // A stub GPU API is only selected for slices within the thresholds
+        let stub_api = Some("stub GPU API");
+        assert_eq!(select_gpu_verify_api(stub_api, 63, 64, 1024), None);
+        assert_eq!(select_gpu_verify_api(stub_api, 64, 64, 1024), stub_api);
+        assert_eq!(select_gpu_verify_api(stub_api, 1024, 64, 1024), stub_api);
+        assert_eq!(select_gpu_verify_api(stub_api, 1025, 64, 1024), None);
+        assert_eq!(select_gpu_verify_api(None::<&str>, 64, 64, 1024), None);
+    }
+
+    #[test]
+    fn test_verify_no_future_blockhash() {
+        let zero = Hash::default();
+        let keypair = Keypair::new();
+        let ticks = create_ticks(3, 4, zero);
+
+        let tx = system_transaction::transfer(&keypair, &keypair.pubkey(), 1, ticks[1].hash);
+        let mut entries = ticks.clone();
+        entries.push(next_entry(&ticks[2].hash, 1, vec![tx.clone()]));
+        assert!(entries.verify(&zero));
+        assert!(entries.verify_no_future_blockhash());
+
+        // the transaction now precedes the entry whose hash it references
+        let entries = vec![next_entry(&zero, 1, vec![tx]), ticks[1].clone()];
+        assert!(!entries.verify_no_future_blockhash());
+    }
+
+    #[test]
+    fn test_verify_cpu_x86_simd_scratch_reuse() {
+        let (has_avx2, has_avx512) = x86_simd_features();
+        if api().is_none() {
+            return;
+        }
+        let zero = Hash::default();
+        for simd_len in [(has_avx2, 8), (has_avx512, 16)]
+            .into_iter()
+            .filter_map(|(supported, simd_len)| supported.then_some(simd_len))
+        {
+            for num_ticks in [100, 3, 64, 17, 200, 1] {
+                let mut entries = create_ticks(num_ticks, 4, zero);
+                assert!(entries.verify_cpu_x86_simd(&zero, simd_len).finish_verify());
+                entries.last_mut().unwrap().hash = Hash::new_unique();
+                assert!(!entries.verify_cpu_x86_simd(&zero, simd_len).finish_verify());
+            }
+        }
+    }
+
+    #[test]
+    fn test_retain_simd_verify_scratch() {
+        let entries = 2 * MAX_RETAINED_SIMD_SCRATCH_ENTRIES;
+        retain_simd_verify_scratch(vec![0; HASH_BYTES * entries], vec![0; entries]);
+        let (hashes_bytes, num_hashes) = SIMD_VERIFY_SCRATCH.with(RefCell::take);
+        assert!(hashes_bytes.is_empty());
+        assert!(hashes_bytes.capacity() <= HASH_BYTES * MAX_RETAINED_SIMD_SCRATCH_ENTRIES);
+        assert!(num_hashes.is_empty());
+        assert!(num_hashes.capacity() <= MAX_RETAINED_SIMD_SCRATCH_ENTRIES);
+
+        // Smaller buffers keep their capacity for reuse
+        retain_simd_verify_scratch(Vec::with_capacity(HASH_BYTES * 64), Vec::with_capacity(64));
+        let (hashes_bytes, num_hashes) = SIMD_VERIFY_SCRATCH.with(RefCell::take);
+        assert!(hashes_bytes.capacity() >= HASH_BYTES * 64);
+        assert!(num_hashes.capacity() >= 64);
+    }
+
+    #[test]
+    fn test_verify_cpu_x86_simd_per_chunk() {
+        let (has_avx2, has_avx512) = x86_simd_features();
+        if api().is_none() {
+            return;
+        }
+        let zero = Hash::default();
+        for simd_len in [(has_avx2, 8), (has_avx512, 16)]
+            .into_iter()
+            .filter_map(|(supported, simd_len)| supported.then_some(simd_len))
+        {
+            let mut entries = create_ticks(5 * simd_len as u64 - 3, 4, zero);
+            assert_eq!(
+                entries.verify_cpu_x86_simd_per_chunk(&zero, simd_len),
+                vec![true; 5]
+            );
+
+            // corrupt an entry in the third chunk
+            entries[2 * simd_len + 1].hash = Hash::new_unique();
+            assert_eq!(
+                entries.verify_cpu_x86_simd_per_chunk(&zero, simd_len),
+                vec![true, true, false, true, true]
+            );
+        }
+    }
+
+    #[test]
+    fn test_entry_from_parts() {
+        let zero = Hash::default();
+        let keypair = Keypair::new();
+        let tx0 = system_transaction::transfer(&keypair, &keypair.pubkey(), 0, zero);
+        let tx1 = system_transaction::transfer(&keypair, &keypair.pubkey(), 1, zero);
+        let prev_hash = Hash::new_unique();
+        let entry = next_entry(&prev_hash, 3, vec![tx0, tx1]);
+        let tx_bytes: Vec<_> = entry
+            .transactions
+            .iter()
+            .map(|tx| bincode::serialize(tx).unwrap())
+            .collect();
+
+        let rebuilt = entry_from_parts(entry.num_hashes, entry.hash, &tx_bytes).unwrap();
+        assert_eq!(rebuilt, entry);
+        assert!(rebuilt.verify(&prev_hash));
+
+        let tick = entry_from_parts(1, entry.hash, &[]).unwrap();
+        assert!(tick.is_tick());
+
+        let mut truncated = tx_bytes;
+        truncated[1].pop();
+        assert!(entry_from_parts(entry.num_hashes, entry.hash, &truncated).is_err());
+    }
+
+    #[test]
+    fn test_verify_with_cache() {
+        let zero = Hash::default();
+        let cache = VerifyCache::new(2);
+        let mut entries = create_ticks(8, 4, zero);
+
+        assert!(entries.verify_with_cache(&zero, &cache));
+        assert_eq!((cache.hits(), cache.misses()), (0, 1));
+        assert!(entries.verify_with_cache(&zero, &cache));
+        assert_eq!((cache.hits(), cache.misses()), (1, 1));
+
+        // a different start hash is a different key
+        assert!(!entries.verify_with_cache(&Hash::new_unique(), &cache));
+        assert_eq!((cache.hits(), cache.misses()), (1, 2));
+
+        // so is a modified slice
+        entries[3].num_hashes += 1;
+        assert!(!entries.verify_with_cache(&zero, &cache));
+        assert_eq!((cache.hits(), cache.misses()), (1, 3));
+        assert!(!entries.verify_with_cache(&zero, &cache));
+        assert_eq!((cache.hits(), cache.misses()), (2, 3));
+    }
+
+    #[test]
+    fn test_verifying_prefix_len() {
+        let zero = Hash::default();
+        let mut entries = create_ticks(8, 4, zero);
+        assert_eq!(entries.verifying_prefix_len(&zero), 8);
+        assert_eq!(entries.verifying_prefix_len(&Hash::new_unique()), 0);
+
+        entries[4].hash = Hash::new_unique();
+        assert_eq!(entries.verifying_prefix_len(&zero), 4);
+        assert!(entries[..4].verify(&zero));
+    }
+
+    #[test]
+    fn test_start_verify_replay() {
+        use solana_sdk::signature::Signature;
+        let verify = Arc::new(
+            |versioned_tx: VersionedTransaction,
+             verification_mode: TransactionVerificationMode|
+             -> Result<SanitizedTransaction> {
+                let message_hash =
+                    if verification_mode == TransactionVerificationMode::FullVerification {
+                        versioned_tx.verify_and_hash_message()?
+                    } else {
+                        versioned_tx.message.hash()
+                    };
+                SanitizedTransaction::try_create(
+                    versioned_tx,
+                    message_hash,
+                    None,
+                    SimpleAddressLoader::Disabled,
+                )
+            },
+        );
+        let replay = |entries: Vec<Entry>, start_hash: &Hash| {
+            start_verify_replay(
+                entries,
+                start_hash,
+                false,
+                true,
+                true,
+                VerifyRecyclers::default(),
+                verify.clone(),
+            )
+            .map(|mut state| state.finish_verify())
+            .unwrap_or(false)
+        };
+
+        let zero = Hash::default();
+        let keypair = Keypair::new();
+        let tx = system_transaction::transfer(&keypair, &keypair.pubkey(), 0, zero);
+        let entries = create_entries_with_ticks(zero, vec![vec![tx.clone()]; 4], 4, 2);
+        assert!(replay(entries.clone(), &zero));
+        assert!(!replay(entries, &Hash::new_unique()));
+
+        // valid PoH over a transaction with a bad signature
+        let mut bad_tx = tx;
+        bad_tx.signatures[0] = Signature::default();
+        let entries = create_entries_with_ticks(zero, vec![vec![bad_tx]; 4], 4, 2);
+        assert!(entries.verify(&zero));
+        assert!(!replay(entries, &zero));
+    }
+
+    #[test]
+    fn test_start_verify_cpu_background() {
+        let zero = Hash::default();
+        let keypair = Keypair::new();
+        let tx = system_transaction::transfer(&keypair, &keypair.pubkey(), 0, zero);
+        let mut entries = create_entries_with_ticks(zero, vec![vec![tx]; 40], 4, 2);
+        entries.insert(0, Entry::new_tick(0, &zero));
+
+        let mut state = start_verify_cpu_background(&entries, &zero);
+        assert_eq!(state.status(), EntryVerificationStatus::Pending);
+        assert_eq!(state.tick_count(), entries.tick_count());
+        assert!(state.finish_verify());
+        assert_eq!(state.status(), EntryVerificationStatus::Success);
+
+        let mut bad_entries = entries.clone();
+        bad_entries[20].hash = Hash::new_unique();
+        assert!(!start_verify_cpu_background(&bad_entries, &zero).finish_verify());
+        assert!(!start_verify_cpu_background(&entries, &Hash::new_unique()).finish_verify());
+
+        let mut hashes: Vec<_> = (0..37).map(|_| Hash::new_unique()).collect();
+        let num_hashes: Vec<_> = (0..37).collect();
+        let expected: Vec<_> = hashes
+            .iter()
+            .zip(&num_hashes)
+            .map(|(hash, num_hashes)| next_hash(hash, *num_hashes, &[]))
+            .collect();
+        hash_poh_chains(&mut hashes, &num_hashes);
+        assert_eq!(hashes, expected);
+    }
+
+    #[test]
+    fn test_entries_content_hash() {
+        let zero = Hash::default();
+        let keypair = Keypair::new();
+        let tx = system_transaction::transfer(&keypair, &keypair.pubkey(), 0, zero);
+        let entries = create_entries_with_ticks(zero, vec![vec![tx]; 2], 4, 1);
+        let content_hash = entries_content_hash(&entries);
+        assert_eq!(content_hash, entries_content_hash(&entries.clone()));
+        assert_ne!(content_hash, entries_content_hash(&entries[1..]));
+
+        let mut modified = entries.clone();
+        modified[1].num_hashes += 1;
+        assert_ne!(content_hash, entries_content_hash(&modified));
+
+        let mut modified = entries.clone();
+        modified[2].hash = Hash::new_unique();
+        assert_ne!(content_hash, entries_content_hash(&modified));
+
+        let mut modified = entries;
+        modified[0].transactions[0]
+            .message
+            .set_recent_blockhash(Hash::new_unique());
+        assert_ne!(content_hash, entries_content_hash(&modified));
+    }
+
+    #[test]
+    fn test_select_gpu_api_no_device() {
+        // Stub api whose probe reports no device: the CPU path must be chosen, and the
+        // probe must only run once
+        let gpu_available = OnceCell::new();
+        let probes = AtomicU64::new(0);
+        let probe = |_: &()| {
+            probes.fetch_add(1, Ordering::Relaxed);
+            false
+        };
+        assert_eq!(select_gpu_api(Some(()), &gpu_available, probe), None);
+        assert_eq!(select_gpu_api(Some(()), &gpu_available, probe), None);
+        assert_eq!(probes.load(Ordering::Relaxed), 1);
+
+        let gpu_available = OnceCell::new();
+        assert_eq!(select_gpu_api(Some(()), &gpu_available, |_| true), Some(()));
+        // Without the lib there is nothing to probe
+        let gpu_available = OnceCell::new();
+        assert_eq!(select_gpu_api(None::<()>, &gpu_available, |_| true), None);
+        assert_eq!(gpu_available.get(), None);
+    }
+
+    #[test]
+    fn test_verify_intra_entry_lock_conflicts() {
+        let zero = Hash::default();
+        let alice = Keypair::new();
+        let bob = Keypair::new();
+        let tx0 = system_transaction::transfer(&alice, &Pubkey::new_unique(), 1, zero);
+        let tx1 = system_transaction::transfer(&bob, &Pubkey::new_unique(), 1, zero);
+        let tx2 = system_transaction::transfer(&alice, &Pubkey::new_unique(), 1, zero);
+
+        // Disjoint writers, and a repeated writer split across entries
+        let entries = vec![
+            next_entry(&zero, 1, vec![tx0.clone(), tx1.clone()]),
+            next_entry(&zero, 1, vec![tx2.clone()]),
+        ];
+        assert_eq!(entries.verify_intra_entry_lock_conflicts(), Ok(()));
+
+        // Both transactions write-lock alice's account as fee payer
+        let entries = vec![
+            next_entry(&zero, 1, vec![tx1]),
+            next_entry(&zero, 1, vec![tx0, tx2]),
+        ];
+        assert_eq!(
+            entries.verify_intra_entry_lock_conflicts(),
+            Err((1, alice.pubkey()))
+        );
+    }
+
+    #[test]
+    fn test_verify_sanitized_transactions() {
+        let verify = Arc::new(
+            |versioned_tx: VersionedTransaction| -> Result<SanitizedTransaction> {
+                let message_hash = versioned_tx.verify_and_hash_message()?;
+                SanitizedTransaction::try_create(
+                    versioned_tx,
+                    message_hash,
+                    None,
+                    SimpleAddressLoader::Disabled,
+                )
+            },
+        );
+        // Sanitize without checking signatures, as a caller that already holds sanitized
+        // transactions would have
+        let sanitize = |entries: &[Entry]| -> Vec<Vec<SanitizedTransaction>> {
+            entries
+                .iter()
+                .map(|entry| {
+                    entry
+                        .transactions
+                        .iter()
+                        .map(|tx| {
+                            SanitizedTransaction::try_create(
+                                tx.clone(),
+                                tx.message.hash(),
+                                None,
+                                SimpleAddressLoader::Disabled,
+                            )
+                            .unwrap()
+                        })
+                        .collect()
+                })
+                .collect()
+        };
+
+        let zero = Hash::default();
+        let valid = vec![
+            next_entry(&zero, 1, vec![test_tx(), test_tx()]),
+            next_entry(&zero, 1, vec![test_tx()]),
+        ];
+        let invalid = vec![
+            next_entry(&zero, 1, vec![test_tx()]),
+            next_entry(&zero, 1, vec![test_tx(), test_invalid_tx()]),
+        ];
+        for entries in [valid, invalid] {
+            let sanitized = sanitize(&entries);
+            assert_eq!(
+                verify_sanitized_transactions(&sanitized).is_ok(),
+                verify_transactions(entries, verify.clone()).is_ok()
+            );
+        }
+        assert!(verify_sanitized_transactions(&sanitize(&[next_entry(
+            &zero,
+            1,
+            vec![test_invalid_tx()]
+        )]))
+        .is_err());
+    }
+
+    #[test]
+    fn test_log_target() {
+        // Operators filter on the target, e.g. `RUST_LOG=solana_entry::verify=off`, so it has
+        // to stay under this crate's name. Asserted directly rather than by capturing output,
+        // since swapping the global logger would race with other tests.
+        assert_eq!(LOG_TARGET, "solana_entry::verify");
+        let crate_name = module_path!().split("::").next().unwrap();
+        assert!(LOG_TARGET.starts_with(&format!("{crate_name}::")));
+    }
+
+    #[test]
+    fn test_infer_tick_num_hashes() {
+        let zero = Hash::default();
+        let mut entries = create_ticks(4, 8, zero);
+        let expected = entries.clone();
+        entries.iter_mut().for_each(|entry| entry.num_hashes = 0);
+
+        assert!(!entries.verify(&zero));
+        // The wrong tick rate fails, leaving the entries untouched
+        assert!(!entries.infer_tick_num_hashes(&zero, 7));
+        assert!(entries.iter().all(|entry| entry.num_hashes == 0));
+
+        assert!(entries.infer_tick_num_hashes(&zero, 8));
+        assert_eq!(entries, expected);
+        assert!(entries.verify(&zero));
+    }
+
+    #[test]
+    fn test_verify_checkpoint() {
+        let zero = Hash::default();
+        let entries = create_ticks(10, 4, zero);
 
-        // Next, swap two transactions and ensure verification fails.
-        e0.transactions[0] = tx1.into(); // <-- attack
-        e0.transactions[1] = tx0.into();
-        assert!(!e0.verify(&zero));
+        let (res, checkpoints) = entries.verify_checkpoint(&zero, 4);
+        assert!(res);
+        assert_eq!(
+            checkpoints,
+            vec![(4, entries[3].hash), (8, entries[7].hash)]
+        );
+        // Resume from the last checkpoint as a fresh run would, only knowing its hash
+        let (index, hash) = checkpoints[1];
+        assert!(entries[..index].verify(&zero));
+        assert!(entries.verify_from_index(index, &hash));
+        assert_eq!(
+            entries.verify_from_index(index, &hash),
+            entries.verify(&zero)
+        );
+        assert!(!entries.verify_from_index(index, &zero));
+        assert!(!entries.verify_from_index(entries.len() + 1, &hash));
+
+        // Checkpoints past the first bad entry can't be trusted
+        let mut bad_entries = entries.clone();
+        bad_entries[5].hash = Hash::new_unique();
+        let (res, checkpoints) = bad_entries.verify_checkpoint(&zero, 4);
+        assert!(!res);
+        assert_eq!(checkpoints, vec![(4, entries[3].hash)]);
+        assert!(!bad_entries.verify_from_index(4, &checkpoints[0].1));
     }
 
     #[test]
-    fn test_transaction_signing() {
+    fn test_total_requested_compute_units() {
+        use solana_sdk::{instruction::Instruction, system_instruction};
+
+        let zero = Hash::default();
+        let payer = Keypair::new();
+        let transfer = system_instruction::transfer(&payer.pubkey(), &Pubkey::new_unique(), 1);
+        let tx = |instructions: &[Instruction]| {
+            Transaction::new_signed_with_payer(instructions, Some(&payer.pubkey()), &[&payer], zero)
+        };
+        // The default limit applies to each instruction that isn't a compute budget one
+        let default_tx = tx(&[
+            transfer.clone(),
+            ComputeBudgetInstruction::set_compute_unit_price(1),
+            transfer.clone(),
+        ]);
+        let limited_tx = tx(&[
+            ComputeBudgetInstruction::set_compute_unit_limit(50_000),
+            transfer.clone(),
+        ]);
+        let over_max_tx = tx(&[
+            ComputeBudgetInstruction::set_compute_unit_limit(MAX_COMPUTE_UNIT_LIMIT + 1),
+            transfer.clone(),
+        ]);
+        let many_instructions_tx = tx(&vec![transfer; 8]);
+
+        let entries = vec![
+            next_entry(&zero, 1, vec![default_tx]),
+            next_entry(&zero, 1, vec![]),
+            next_entry(&zero, 1, vec![limited_tx]),
+        ];
+        assert_eq!(
+            entries.total_requested_compute_units(),
+            2 * u64::from(DEFAULT_INSTRUCTION_COMPUTE_UNIT_LIMIT) + 50_000
+        );
+        assert_eq!(entries[1..2].total_requested_compute_units(), 0);
+
+        // Limits are capped per transaction, whether requested or defaulted
+        let entries = vec![next_entry(
+            &zero,
+            1,
+            vec![over_max_tx, many_instructions_tx],
+        )];
+        assert_eq!(
+            entries.total_requested_compute_units(),
+            2 * u64::from(MAX_COMPUTE_UNIT_LIMIT)
+        );
+    }
+
+    #[test]
+    fn test_verify_failing_entries() {
+        let zero = Hash::default();
+        let mut entries = create_ticks(8, 4, zero);
+        assert!(entries.verify_failing_entries(&zero).is_empty());
+
+        entries[2].num_hashes += 1;
+        entries[6].num_hashes += 1;
+        let failing = entries.verify_failing_entries(&zero);
+        assert_eq!(failing.len(), 2);
+        assert!(std::ptr::eq(failing[0], &entries[2]));
+        assert!(std::ptr::eq(failing[1], &entries[6]));
+    }
+
+    #[test]
+    fn test_hashes_equal() {
+        let hash = Hash::new_unique();
+        assert!(hashes_equal(&hash, &Hash::new_from_array(hash.to_bytes())));
+        assert!(!hashes_equal(&hash, &Hash::new_unique()));
+        let mut bytes = hash.to_bytes();
+        bytes[HASH_BYTES - 1] ^= 1;
+        assert!(!hashes_equal(&hash, &Hash::new_from_array(bytes)));
+
+        // Verification results are the same with or without `ct-compare`
+        let zero = Hash::default();
+        let mut entries = create_ticks(8, 4, zero);
+        entries.push(next_entry(&entries[7].hash, 2, vec![test_tx()]));
+        assert!(entries.verify(&zero));
+        assert!(entries.verify_cpu(&zero).finish_verify());
+        assert!(entries.verify_with_chain(&zero).0);
+        assert!(!entries.verify(&hash));
+        entries[8].hash = hash;
+        assert!(!entries.verify(&zero));
+        assert!(!entries.verify_cpu(&zero).finish_verify());
+        assert!(!entries.verify_with_chain(&zero).0);
+    }
+
+    #[test]
+    fn test_entries_to_serialized_chunks() {
+        let zero = Hash::default();
+        let entries = create_ticks(10, 4, zero);
+        let entry_bytes = bincode::serialized_size(&entries[0]).unwrap() as usize;
+        let max_chunk_bytes = 8 + 3 * entry_bytes;
+
+        let chunks = entries_to_serialized_chunks(&entries, max_chunk_bytes);
+        assert_eq!(chunks.len(), 4);
+        assert!(chunks.iter().all(|chunk| chunk.len() <= max_chunk_bytes));
+        let deserialized: Vec<Entry> = chunks
+            .iter()
+            .flat_map(|chunk| bincode::deserialize::<Vec<Entry>>(chunk).unwrap())
+            .collect();
+        assert_eq!(deserialized, entries);
+
+        assert!(entries_to_serialized_chunks(&[], max_chunk_bytes).is_empty());
+    }
+
+    #[test]
+    fn test_entries_to_serialized_chunks_oversized_entry() {
+        let zero = Hash::default();
+        let tick = next_entry(&zero, 1, vec![]);
+        let big = next_entry(&tick.hash, 1, vec![test_tx(); 8]);
+        let entries = vec![tick.clone(), big.clone(), tick.clone()];
+        let max_chunk_bytes = 8 + 2 * bincode::serialized_size(&tick).unwrap() as usize;
+        assert!(bincode::serialized_size(&big).unwrap() as usize > max_chunk_bytes);
+
+        let chunks = entries_to_serialized_chunks(&entries, max_chunk_bytes);
+        assert_eq!(chunks.len(), 3);
+        assert!(chunks[1].len() > max_chunk_bytes);
+        for (chunk, entry) in chunks.iter().zip(entries) {
+            assert_eq!(
+                bincode::deserialize::<Vec<Entry>>(chunk).unwrap(),
+                vec![entry]
+            );
+        }
+    }
+
+    #[test]
+    fn test_entry_counts() {
+        let zero = Hash::default();
+        let entries: Vec<_> = [0, 3, 1, 4]
+            .into_iter()
+            .map(|num_txs| next_entry(&zero, 1, vec![test_tx(); num_txs]))
+            .collect();
+        let counts = |entries: &[Entry]| {
+            entries
+                .iter()
+                .map(EntryCounts::new)
+                .fold(EntryCounts::default(), EntryCounts::merge)
+        };
+        let expected = EntryCounts {
+            num_entries: 4,
+            num_ticks: 1,
+            num_txs: 8,
+            min_txs: 0,
+            max_txs: 4,
+        };
+        assert_eq!(counts(&entries), expected);
+        assert_eq!(counts(&entries[..2]).merge(counts(&entries[2..])), expected);
+        assert_eq!(EntryCounts::default().merge(expected), expected);
+
+        // Every verify path gathers the same counts while staging the slice
+        let mut entries = entries;
+        let mut prev_hash = zero;
+        for entry in &mut entries {
+            *entry = next_entry(&prev_hash, 1, entry.transactions.clone());
+            prev_hash = entry.hash;
+        }
+        assert_eq!(entries.verify_cpu_generic(&zero).entry_counts, expected);
+        let mut state = start_verify_cpu_background(&entries, &zero);
+        assert!(state.finish_verify());
+        assert_eq!(state.entry_counts, expected);
+        assert_eq!(
+            entries
+                .verify_into(
+                    &zero,
+                    &mut PinnedVec::default(),
+                    &mut PinnedVec::default(),
+                    0,
+                    usize::MAX
+                )
+                .entry_counts,
+            expected
+        );
+    }
+
+    #[test]
+    fn test_verify_fork() {
+        let zero = Hash::default();
+        let prefix = create_ticks(4, 4, zero);
+        let last_hash = prefix.last().unwrap().hash;
+        let branch_a = vec![next_entry(&last_hash, 2, vec![test_tx()])];
+        let mut branch_b = create_ticks(3, 4, last_hash);
+        assert_ne!(branch_a[0].hash, branch_b[0].hash);
+        assert_eq!(verify_fork(&last_hash, &branch_a, &branch_b), (true, true));
+
+        branch_b[1].num_hashes += 1;
+        assert_eq!(verify_fork(&last_hash, &branch_a, &branch_b), (true, false));
+        assert_eq!(verify_fork(&zero, &branch_a, &branch_b), (false, false));
+    }
+
+    #[test]
+    fn test_verify_cpu_x86_simd_short_slices() {
+        let (has_avx2, _) = x86_simd_features();
+        if api().is_none() || !has_avx2 {
+            return;
+        }
+        let zero = Hash::default();
+        for num_entries in 8..=48 {
+            let mut entries = create_ticks(num_entries, 4, zero);
+            // Mix in a transaction entry so both the tick and mixin paths are compared
+            let last_hash = entries.last().unwrap().hash;
+            entries.push(next_entry(&last_hash, 2, vec![test_tx()]));
+            for bad_index in [
+                None,
+                Some(0),
+                Some(num_entries as usize / 2),
+                Some(num_entries as usize),
+            ] {
+                let mut entries = entries.clone();
+                if let Some(bad_index) = bad_index {
+                    entries[bad_index].hash = Hash::new_unique();
+                }
+                assert_eq!(
+                    entries.verify_cpu_x86_simd(&zero, 8).finish_verify(),
+                    entries.verify_cpu_generic(&zero).finish_verify(),
+                );
+                assert_eq!(
+                    entries.verify_cpu(&zero).finish_verify(),
+                    bad_index.is_none()
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_verify_with_hasher() {
         use solana_sdk::signature::Signature;
+        // Cheap stand-in for PoH: fold num_hashes and the mixin into the previous hash
+        let stub_hasher = |prev_hash: &Hash, num_hashes: u64, mixin: Option<Hash>| {
+            let mut bytes = prev_hash.to_bytes();
+            for (byte, n) in bytes.iter_mut().zip(num_hashes.to_le_bytes()) {
+                *byte = byte.wrapping_add(n).wrapping_add(1);
+            }
+            if let Some(mixin) = mixin {
+                for (byte, m) in bytes.iter_mut().zip(mixin.to_bytes()) {
+                    *byte ^= m;
+                }
+            }
+            Hash::new_from_array(bytes)
+        };
+
         let zero = Hash::default();
+        let mut prev_hash = zero;
+        let mut entries: Vec<_> = (0..100_000u64)
+            .map(|i| {
+                let transactions = if i % 1000 == 0 {
+                    vec![VersionedTransaction::from(test_tx())]
+                } else {
+                    vec![]
+                };
+                let mixin = (!transactions.is_empty()).then(|| hash_transactions(&transactions));
+                let hash = stub_hasher(&prev_hash, i % 7, mixin);
+                prev_hash = hash;
+                Entry {
+                    num_hashes: i % 7,
+                    hash,
+                    transactions,
+                }
+            })
+            .collect();
+        assert!(entries.verify_with_hasher(&zero, stub_hasher));
+        assert!(!entries.verify_with_hasher(&Hash::new_unique(), stub_hasher));
+
+        entries[50_000].num_hashes += 1;
+        assert!(!entries.verify_with_hasher(&zero, stub_hasher));
+        entries[50_000].num_hashes -= 1;
+        entries[42_000].transactions[0].signatures[0] = Signature::default();
+        assert!(!entries.verify_with_hasher(&zero, stub_hasher));
+    }
 
-        let keypair = Keypair::new();
-        let tx0 = system_transaction::transfer(&keypair, &keypair.pubkey(), 0, zero);
-        let tx1 = system_transaction::transfer(&keypair, &keypair.pubkey(), 1, zero);
+    #[test]
+    fn test_verify_throughput() {
+        let zero = Hash::default();
+        let entries = create_ticks(256, 1000, zero);
+        let report = entries.verify_throughput(&zero);
+        assert!(report.verified);
+        assert!(report.hashes_per_second > 0.0);
+        assert!(report.entries_per_second > 0.0);
+        assert!(report.hashes_per_second > report.entries_per_second);
+        if gpu_api().is_none() {
+            assert_eq!(report.path, cpu_verify_path(entries.len()));
+        }
+        if api().is_none() && gpu_api().is_none() {
+            assert_eq!(report.path, VerifyPath::Generic);
+        }
 
-        // Verify entry with 2 transactions
-        let mut e0 = vec![Entry::new(&zero, 0, vec![tx0, tx1])];
-        assert!(e0.verify(&zero));
+        assert!(!entries.verify_throughput(&Hash::new_unique()).verified);
+    }
 
-        // Clear signature of the first transaction, see that it does not verify
-        let orig_sig = e0[0].transactions[0].signatures[0];
-        e0[0].transactions[0].signatures[0] = Signature::default();
-        assert!(!e0.verify(&zero));
+    #[test]
+    fn test_zero_hash_transaction_entry() {
+        let zero = Hash::default();
+        // A passthrough would accept the computed hash as is, but transactions are mixed in
+        let mut malformed = next_entry(&zero, 1, vec![test_tx()]);
+        malformed.num_hashes = 0;
+        malformed.hash = zero;
+        assert!(!compare_hashes(zero, &malformed));
+        let entries = vec![malformed];
+        assert!(!entries.verify_cpu_generic(&zero).finish_verify());
+        let (has_avx2, _) = x86_simd_features();
+        if api().is_some() && has_avx2 {
+            assert!(!entries.verify_cpu_x86_simd(&zero, 8).finish_verify());
+        }
 
-        // restore original signature
-        e0[0].transactions[0].signatures[0] = orig_sig;
-        assert!(e0.verify(&zero));
+        // Every path hashes a transaction entry with no hashes like `next_hash`, i.e. as if it
+        // had a single hash
+        let mut entry = next_entry(&zero, 1, vec![test_tx()]);
+        entry.num_hashes = 0;
+        assert_eq!(next_hash(&zero, 0, &entry.transactions), entry.hash);
+        assert!(compare_hashes(zero, &entry));
+        assert!(entry.verify(&zero));
+        let entries = vec![entry];
+        assert!(matches!(
+            verify_actions(&entries)[..],
+            [(VerifyAction::Mixin(_), _)]
+        ));
+        assert!(entries.verify_cpu_generic(&zero).finish_verify());
+        assert!(entries.verify_with_steps(&zero).0);
+        assert_eq!(entries.verify_trace(&zero), vec![entries[0].hash]);
+        assert_eq!(
+            entries.verify_with_chain(&zero),
+            (true, vec![entries[0].hash])
+        );
+        let dump_path = std::env::temp_dir().join("test_zero_hash_transaction_entry.json");
+        assert!(entries.verify_and_dump(&zero, &dump_path).unwrap());
+        assert!(entries.verify_with_hasher(&zero, next_hash_with_mixin));
+        if api().is_some() && has_avx2 {
+            assert!(entries.verify_cpu_x86_simd(&zero, 8).finish_verify());
+        }
+    }
 
-        // Resize signatures and see verification fails.
-        let len = e0[0].transactions[0].signatures.len();
-        e0[0].transactions[0]
-            .signatures
-            .resize(len - 1, Signature::default());
-        assert!(!e0.verify(&zero));
+    #[cfg(feature = "async")]
+    #[tokio::test]
+    async fn test_verify_stream_async() {
+        use futures::stream;
 
-        // Pass an entry with no transactions
-        let e0 = vec![Entry::new(&zero, 0, vec![])];
-        assert!(e0.verify(&zero));
+        let zero = Hash::default();
+        let batch0 = create_ticks(4, 4, zero);
+        let batch1 = create_ticks(4, 4, batch0.last().unwrap().hash);
+        assert!(
+            verify_stream_async(
+                stream::iter(vec![batch0.clone(), vec![], batch1.clone()]),
+                zero
+            )
+            .await
+        );
+        assert!(verify_stream_async(stream::iter(vec![]), zero).await);
+        // Out of order batches break the chain
+        assert!(!verify_stream_async(stream::iter(vec![batch1, batch0]), zero).await);
     }
 
     #[test]
-    fn test_next_entry() {
+    fn test_verify_following() {
         let zero = Hash::default();
-        let tick = next_entry(&zero, 1, vec![]);
-        assert_eq!(tick.num_hashes, 1);
-        assert_ne!(tick.hash, zero);
+        let parent = next_entry(&zero, 4, vec![test_tx()]);
+        let entries = create_ticks(4, 4, parent.hash);
+        assert!(entries.verify_following(&parent));
+        assert_eq!(
+            entries.verify_following(&parent),
+            entries.verify(&parent.hash)
+        );
 
-        let tick = next_entry(&zero, 0, vec![]);
-        assert_eq!(tick.num_hashes, 0);
-        assert_eq!(tick.hash, zero);
+        let other_parent = next_entry(&zero, 5, vec![]);
+        assert!(!entries.verify_following(&other_parent));
+        // The parent's own start hash is the classic off-by-one
+        assert!(!entries.verify(&zero));
+    }
 
-        let keypair = Keypair::new();
-        let tx0 = system_transaction::transfer(&keypair, &Pubkey::new_unique(), 42, zero);
-        let entry0 = next_entry(&zero, 1, vec![tx0.clone()]);
-        assert_eq!(entry0.num_hashes, 1);
-        assert_eq!(entry0.hash, next_hash(&zero, 1, &[tx0.into()]));
+    #[test]
+    fn test_verify_tick_run() {
+        let zero = Hash::default();
+        for (num_ticks, hashes_per_tick) in [(1, 1), (8, 4), (64, 12)] {
+            let ticks = create_ticks(num_ticks, hashes_per_tick, zero);
+            let last = ticks.last().unwrap().hash;
+            assert!(ticks.verify(&zero));
+            assert!(verify_tick_run(&zero, num_ticks, hashes_per_tick, &last));
+            assert!(!verify_tick_run(
+                &zero,
+                num_ticks + 1,
+                hashes_per_tick,
+                &last
+            ));
+            assert!(!verify_tick_run(
+                &zero,
+                num_ticks,
+                hashes_per_tick + 1,
+                &last
+            ));
+            assert!(!verify_tick_run(
+                &Hash::new_unique(),
+                num_ticks,
+                hashes_per_tick,
+                &last
+            ));
+        }
+        // Zero-hash ticks don't advance the chain
+        let ticks = create_ticks(3, 0, zero);
+        assert!(verify_tick_run(&zero, 3, 0, &ticks.last().unwrap().hash));
+        assert!(verify_tick_run(&zero, 0, 4, &zero));
+    }
+
+    #[test]
+    fn test_verify_numa_aware() {
+        let node_pools: Vec<_> = (0..2)
+            .map(|_| {
+                Arc::new(
+                    rayon::ThreadPoolBuilder::new()
+                        .num_threads(2)
+                        .build()
+                        .unwrap(),
+                )
+            })
+            .collect();
+        let zero = Hash::default();
+        for num_ticks in [0, 1, 2, 7, 64] {
+            let mut entries = create_ticks(num_ticks, 4, zero);
+            assert!(entries.verify_numa_aware(&zero, &node_pools));
+            assert!(entries.verify_numa_aware(&zero, &node_pools[..1]));
+            assert!(entries.verify_numa_aware(&zero, &[]));
+            if let Some(entry) = entries.last_mut() {
+                entry.hash = Hash::new_unique();
+                assert!(!entries.verify_numa_aware(&zero, &node_pools));
+            }
+        }
+
+        // Corrupting the first entry of the second partition breaks the chain at the boundary
+        let mut entries = create_ticks(8, 4, zero);
+        entries[4].num_hashes += 1;
+        assert_eq!(
+            entries.verify_numa_aware(&zero, &node_pools),
+            entries.verify(&zero)
+        );
+        assert!(!entries.verify_numa_aware(&zero, &node_pools));
+    }
+
+    #[test]
+    fn test_verify_profile() {
+        let profile = VerifyProfile::new(400, 100, true);
+        assert_eq!(profile.ratio, 0.25);
+        assert_eq!(VerifyProfile::new(3, 6, true).ratio, 2.0);
+        assert_eq!(VerifyProfile::new(0, 5, true).ratio, 5.0);
+        assert_eq!(VerifyProfile::new(0, 0, true).ratio, 0.0);
+
+        let zero = Hash::default();
+        let entries = create_ticks(64, 100, zero);
+        let profile = entries.verify_profile(&zero);
+        assert!(profile.verified);
+        assert_eq!(
+            profile.ratio,
+            profile.sig_us as f64 / profile.poh_us.max(1) as f64
+        );
+        assert!(!entries.verify_profile(&Hash::new_unique()).verified);
+    }
+
+    #[test]
+    fn test_verify_trace() {
+        let zero = Hash::default();
+        let mut entries = create_ticks(8, 4, zero);
+        let last_hash = entries.last().unwrap().hash;
+        entries.push(next_entry(&last_hash, 3, vec![test_tx()]));
+
+        let trace = entries.verify_trace(&zero);
+        assert_eq!(trace.len(), entries.len());
+        assert!(trace
+            .iter()
+            .zip(&entries)
+            .all(|(hash, entry)| *hash == entry.hash));
+        assert_eq!(trace, entries.verify_with_chain(&zero).1);
+
+        // Each hash is recomputed from the recorded predecessor, so only the corrupted entry
+        // and the one chained from it differ
+        let expected = trace;
+        entries[3].hash = Hash::new_unique();
+        let trace = entries.verify_trace(&zero);
+        assert_eq!(trace.len(), entries.len());
+        for (i, (hash, entry)) in trace.iter().zip(&entries).enumerate() {
+            assert_eq!(*hash == entry.hash, i != 3 && i != 4);
+        }
+        assert_eq!(trace[3], expected[3]);
+    }
+
+    #[test]
+    fn test_verify_distinct_fee_payers_per_entry() {
+        let zero = Hash::default();
+        let alice = Keypair::new();
+        let bob = Keypair::new();
+        let carol = Pubkey::new_unique();
+        let tx0 = system_transaction::transfer(&alice, &carol, 1, zero);
+        let tx1 = system_transaction::transfer(&bob, &carol, 1, zero);
+        let tx2 = system_transaction::transfer(&alice, &carol, 2, zero);
+
+        let entries = vec![
+            next_entry(&zero, 1, vec![tx0.clone(), tx1.clone()]),
+            next_entry(&zero, 1, vec![tx2.clone()]),
+        ];
+        assert_eq!(entries.verify_distinct_fee_payers_per_entry(), Ok(()));
+
+        let entries = vec![
+            next_entry(&zero, 1, vec![tx1]),
+            next_entry(&zero, 1, vec![tx0, tx2]),
+        ];
+        assert_eq!(
+            entries.verify_distinct_fee_payers_per_entry(),
+            Err((1, alice.pubkey()))
+        );
+    }
+
+    #[test]
+    fn test_finish_verify_cancellable() {
+        // Stands in for a GPU thread stuck waiting on the device until `release` is dropped
+        let gpu_state = |release: Receiver<()>| EntryVerificationState {
+            verification_status: EntryVerificationStatus::Pending,
+            poh_duration_us: 0,
+            transaction_duration_us: 0,
+            gpu_wait_us: 0,
+            gpu_compute_us: 0,
+            entry_counts: EntryCounts::default(),
+            device_verification_data: DeviceVerificationData::Gpu(GpuVerificationData {
+                thread_h: Some(thread::spawn(move || {
+                    let _ = release.recv();
+                    (3, 5)
+                })),
+                hashes: Some(Arc::new(Mutex::new(PinnedVec::default()))),
+                verifications: Some(vec![]),
+            }),
+        };
+
+        let cancel = AtomicBool::new(false);
+        let (release_sender, release) = unbounded();
+        let mut state = gpu_state(release);
+        let res = thread::scope(|scope| {
+            scope.spawn(|| {
+                thread::sleep(Duration::from_millis(20));
+                cancel.store(true, Ordering::Relaxed);
+            });
+            state.finish_verify_cancellable(&cancel)
+        });
+        assert_eq!(res, None);
+        assert_eq!(state.status(), EntryVerificationStatus::Failure);
+        assert!(!state.finish_verify());
+        drop(release_sender);
+
+        // Finishes normally when not cancelled
+        let cancel = AtomicBool::new(false);
+        let (release_sender, release) = unbounded();
+        let mut state = gpu_state(release);
+        drop(release_sender);
+        assert_eq!(state.finish_verify_cancellable(&cancel), Some(true));
+        assert_eq!(state.status(), EntryVerificationStatus::Success);
+        assert_eq!(state.gpu_wait_us(), 3);
+        assert_eq!(state.gpu_compute_us(), 5);
+        assert!(state.poh_duration_us() >= 8);
+
+        let zero = Hash::default();
+        let mut state = create_ticks(4, 4, zero).verify_cpu(&zero);
+        assert_eq!(
+            state.finish_verify_cancellable(&AtomicBool::new(true)),
+            Some(true)
+        );
+    }
+
+    #[test]
+    fn test_diagnose_poh_lib() {
+        let report = diagnose_poh_lib(Path::new("/nonexistent/libpoh-simd.so"));
+        assert_eq!(report.len(), 1);
+        assert!(report[0].starts_with("unable to load"));
+
+        // A library that loads but has none of the kernels
+        #[cfg(target_os = "linux")]
+        assert_eq!(
+            diagnose_poh_lib(Path::new("libc.so.6")),
+            vec![
+                "poh_verify_many_simd_avx512skx: absent",
+                "poh_verify_many_simd_avx2: absent",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_verify_variable_ticks_per_slot() {
+        let zero = Hash::default();
+        // Two slots of 4 ticks of 4 hashes, then two slots of 2 ticks of 8 hashes
+        let mut entries = create_ticks(8, 4, zero);
+        entries.extend(create_ticks(4, 8, entries.last().unwrap().hash));
+        let schedule = [(0, 4, 4), (8, 8, 2)];
+        assert!(entries.verify_variable_ticks_per_slot(&zero, &schedule));
+        assert!(!entries.verify_variable_ticks_per_slot(&Hash::new_unique(), &schedule));
+
+        // A single schedule can't describe the slice
+        assert!(!entries.verify_variable_ticks_per_slot(&zero, &[(0, 4, 4)]));
+        assert!(!entries.verify_variable_ticks_per_slot(&zero, &[(0, 8, 2)]));
+        // The change doesn't land on a slot boundary
+        assert!(!entries.verify_variable_ticks_per_slot(&zero, &[(0, 4, 4), (6, 8, 2)]));
+        // Malformed schedules
+        assert!(!entries.verify_variable_ticks_per_slot(&zero, &[]));
+        assert!(!entries.verify_variable_ticks_per_slot(&zero, &[(1, 4, 4), (8, 8, 2)]));
+        assert!(!entries.verify_variable_ticks_per_slot(&zero, &[(0, 4, 4), (0, 8, 2)]));
+        assert!(!entries.verify_variable_ticks_per_slot(&zero, &[(0, 4, 4), (13, 8, 2)]));
+    }
+
+    #[test]
+    fn test_verify_and_serialize() {
+        let zero = Hash::default();
+        let mut entries = create_ticks(4, 4, zero);
+        let last_hash = entries.last().unwrap().hash;
+        entries.push(next_entry(&last_hash, 2, vec![test_tx(), test_tx()]));
+
+        let mut bytes = vec![];
+        assert!(entries.verify_and_serialize(&zero, &mut bytes).unwrap());
+        assert_eq!(bytes, bincode::serialize(&entries).unwrap());
+        assert_eq!(bincode::deserialize::<Vec<Entry>>(&bytes).unwrap(), entries);
+
+        let mut bytes = vec![];
+        assert!(!entries
+            .verify_and_serialize(&Hash::new_unique(), &mut bytes)
+            .unwrap());
+        assert!(bytes.is_empty());
+    }
+
+    #[test]
+    fn test_bench_hash_transactions() {
+        let counts = [1, 64, 16_384];
+        let timings = bench_hash_transactions(&counts);
+        assert_eq!(
+            timings.iter().map(|(count, _)| *count).collect::<Vec<_>>(),
+            counts
+        );
+        // Tens of thousands of hashes can't beat a single one
+        assert!(timings[2].1 >= timings[0].1);
+        assert!(bench_hash_transactions(&[]).is_empty());
+    }
+
+    #[test]
+    fn test_verify_multisig_transactions() {
+        use {solana_perf::test_tx::test_multisig_tx, solana_sdk::signature::Signature};
+
+        let zero = Hash::default();
+        let transactions = vec![test_multisig_tx(), test_tx(), test_multisig_tx()];
+        assert_eq!(transactions[0].signatures.len(), 2);
+        let entry = next_entry(&zero, 2, transactions);
+        let next = next_entry(&entry.hash, 1, vec![test_multisig_tx()]);
+        let entries = vec![entry, next];
+        assert!(entries.verify(&zero));
+        assert!(entries.verify_cpu_generic(&zero).finish_verify());
+
+        // Every signature of a multisig transaction is a leaf of the Merkle tree
+        for (entry_index, tx_index) in [(0, 0), (0, 2), (1, 0)] {
+            for signature_index in 0..2 {
+                let mut entries = entries.clone();
+                entries[entry_index].transactions[tx_index].signatures[signature_index] =
+                    Signature::default();
+                assert!(!entries.verify(&zero));
+                assert!(!entries.verify_cpu_generic(&zero).finish_verify());
+            }
+        }
+
+        // Signatures are ordered leaves, so swapping them within a transaction changes the
+        // root
+        let mut swapped = entries[1].transactions.clone();
+        swapped[0].signatures.swap(0, 1);
+        assert_ne!(
+            hash_transactions(&swapped),
+            hash_transactions(&entries[1].transactions)
+        );
+        let mut entries = entries;
+        entries[1].transactions = swapped;
+        assert!(!entries.verify(&zero));
+    }
+
+    #[test]
+    fn test_set_transactions() {
+        let zero = Hash::default();
+        let mut entry = next_entry(&zero, 3, vec![test_tx()]);
+        let old_hash = entry.hash;
+
+        entry.set_transactions(&zero, vec![test_tx(), test_tx()]);
+        assert_eq!(entry.num_hashes, 3);
+        assert_eq!(entry.transactions.len(), 2);
+        assert!(entry.verify(&zero));
+        assert_ne!(entry.hash, old_hash);
+
+        // Adding transactions to a zero-hash tick needs a hash to mix them in
+        let mut entry = Entry::new_tick(0, &zero);
+        entry.set_transactions(&zero, vec![test_tx()]);
+        assert_eq!(entry.num_hashes, 1);
+        assert!(entry.verify(&zero));
+
+        entry.set_transactions(&zero, vec![]);
+        assert!(entry.is_tick());
+        assert!(entry.verify(&zero));
+    }
+
+    #[test]
+    fn test_flag_outlier_hash_counts() {
+        let zero = Hash::default();
+        let mut entries = create_ticks(9, 10, zero);
+        assert!(entries.flag_outlier_hash_counts(100).is_empty());
+
+        entries[4].num_hashes = 100_000;
+        entries[7].num_hashes = 1_000;
+        assert_eq!(entries.flag_outlier_hash_counts(100), vec![4]);
+        assert_eq!(entries.flag_outlier_hash_counts(10), vec![4, 7]);
+        assert!(Vec::<Entry>::new().flag_outlier_hash_counts(100).is_empty());
+    }
+
+    #[test]
+    fn test_total_serialized_size() {
+        let zero = Hash::default();
+        let mut entries = create_ticks(4, 4, zero);
+        let last_hash = entries.last().unwrap().hash;
+        entries.push(next_entry(&last_hash, 2, vec![test_tx(), test_tx()]));
+        assert_eq!(
+            entries.total_serialized_size(),
+            bincode::serialized_size(&entries).unwrap()
+        );
+        assert_eq!(
+            entries[..0].total_serialized_size(),
+            bincode::serialized_size(&Vec::<Entry>::new()).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_start_verify_max_gpu_verify_entries() {
+        let zero = Hash::default();
+        let recyclers = VerifyRecyclers::default();
+        assert_eq!(
+            recyclers.max_gpu_verify_entries(),
+            DEFAULT_MAX_GPU_VERIFY_ENTRIES
+        );
+        let recyclers = recyclers
+            .with_min_gpu_verify_entries(1)
+            .with_max_gpu_verify_entries(16);
+        assert_eq!(recyclers.max_gpu_verify_entries(), 16);
+
+        // Over the cap the CPU path is taken, whether or not a GPU is available
+        let entries = create_ticks(17, 4, zero);
+        let mut state = entries.start_verify(&zero, recyclers.clone());
+        assert!(matches!(
+            state.device_verification_data,
+            DeviceVerificationData::Cpu()
+        ));
+        assert!(state.finish_verify());
+
+        let mut state = create_ticks(17, 4, zero).start_verify(&Hash::new_unique(), recyclers);
+        assert!(!state.finish_verify());
+    }
+
+    #[test]
+    fn test_verify_and_partition_slots() {
+        let zero = Hash::default();
+        // Two slots of two ticks, with a transaction entry in the second slot
+        let mut entries = create_ticks(3, 4, zero);
+        let last_hash = entries.last().unwrap().hash;
+        entries.push(next_entry(&last_hash, 2, vec![test_tx()]));
+        let last_hash = entries.last().unwrap().hash;
+        entries.extend(create_ticks(1, 2, last_hash));
+        assert_eq!(
+            entries.verify_and_partition_slots(&zero, 2),
+            Ok(vec![0..2, 2..5])
+        );
+        // A partial slot at the end
+        assert_eq!(
+            entries[..4].verify_and_partition_slots(&zero, 2),
+            Ok(vec![0..2, 2..4])
+        );
+
+        entries[3].hash = Hash::new_unique();
+        assert_eq!(entries.verify_and_partition_slots(&zero, 2), Err(3));
+        assert_eq!(
+            entries.verify_and_partition_slots(&Hash::new_unique(), 2),
+            Err(0)
+        );
+    }
+
+    #[test]
+    fn test_verify_transaction_sizes() {
+        use solana_sdk::packet::PACKET_DATA_SIZE;
+
+        let zero = Hash::default();
+        let mut entries = vec![
+            next_entry(&zero, 1, vec![test_tx(), test_tx()]),
+            next_entry(&zero, 1, vec![test_tx(), test_tx()]),
+        ];
+        assert_eq!(entries.verify_transaction_sizes(PACKET_DATA_SIZE), Ok(()));
+
+        let mut oversized = test_tx();
+        oversized.message.instructions[0].data = vec![0; PACKET_DATA_SIZE];
+        entries[1].transactions[1] = VersionedTransaction::from(oversized);
+        assert_eq!(
+            entries.verify_transaction_sizes(PACKET_DATA_SIZE),
+            Err((1, 1))
+        );
+    }
+
+    #[test]
+    fn test_create_ticks_continuing() {
+        let zero = Hash::default();
+        let mut expected = create_ticks(3, 4, zero);
+        expected.extend(create_ticks(2, 4, expected.last().unwrap().hash));
+
+        let mut poh = Poh::new(zero, Some(4));
+        let mut ticks = create_ticks_continuing(&mut poh, 3, 4);
+        ticks.extend(create_ticks_continuing(&mut poh, 2, 4));
+        assert_eq!(ticks, expected);
+        assert_eq!(poh.hash, expected.last().unwrap().hash);
+
+        // Low power mode ticks on every call
+        let mut poh = Poh::new(zero, None);
+        assert_eq!(create_ticks_continuing(&mut poh, 3, 4), expected[..3]);
+    }
+
+    #[test]
+    fn test_verify_bitset() {
+        let zero = Hash::default();
+        let mut entries = create_ticks(150, 2, zero);
+        assert_eq!(entries.verify_bitset(&zero).len(), 150);
+        let bits = entries.verify_bitset(&zero);
+        assert!((0..150).all(|i| bits.get_bit(i)));
+
+        entries[3].hash = Hash::new_unique();
+        entries[100].num_hashes = 3;
+        let bits = entries.verify_bitset(&zero);
+        for i in 0..150 {
+            // Entry 4 no longer links to the corrupted hash of entry 3
+            let expected = ![3, 4, 100].contains(&i);
+            assert_eq!(bits.get_bit(i as u64), expected, "entry {i}");
+        }
+        assert!(!entries.verify_bitset(&Hash::new_unique()).get_bit(0));
+        assert!(entries[..0].verify_bitset(&zero).is_empty());
+    }
+
+    #[test]
+    fn test_verify_no_identity_links() {
+        let zero = Hash::default();
+        let mut entries = create_ticks(4, 2, zero);
+        let last_hash = entries.last().unwrap().hash;
+        entries.push(next_entry(&last_hash, 1, vec![test_tx()]));
+        assert!(entries.verify_no_identity_links(&zero));
+        // A zero-hash tick legitimately repeats its predecessor's hash
+        assert!([Entry::new_tick(0, &zero)].verify_no_identity_links(&zero));
+
+        let mut bad = entries.clone();
+        bad[2].hash = bad[1].hash;
+        assert!(!bad.verify_no_identity_links(&zero));
+
+        let mut bad = entries.clone();
+        bad[4].hash = bad[3].hash;
+        assert!(!bad.verify_no_identity_links(&zero));
+
+        assert!(!entries.verify_no_identity_links(&entries[0].hash));
+    }
+
+    #[test]
+    fn test_calibrate_gpu_threshold() {
+        let threshold = calibrate_gpu_threshold();
+        if gpu_api().is_none() {
+            assert_eq!(threshold, usize::MAX);
+        } else {
+            assert!(threshold > 0);
+            assert!(threshold <= MAX_CALIBRATION_ENTRIES || threshold == usize::MAX);
+        }
+    }
+
+    #[test]
+    fn test_verify_with_committed_sig_roots() {
+        let zero = Hash::default();
+        let mut entries = create_ticks(2, 4, zero);
+        let last_hash = entries.last().unwrap().hash;
+        entries.push(next_entry(&last_hash, 2, vec![test_tx(), test_tx()]));
+        let last_hash = entries.last().unwrap().hash;
+        entries.push(next_entry(&last_hash, 1, vec![test_tx()]));
+        entries.extend(create_ticks(1, 4, entries.last().unwrap().hash));
+        let roots: Vec<_> = entries
+            .iter()
+            .map(|entry| (!entry.is_tick()).then(|| hash_transactions(&entry.transactions)))
+            .collect();
+
+        let mut stripped = entries.clone();
+        for tx in stripped
+            .iter_mut()
+            .flat_map(|entry| &mut entry.transactions)
+        {
+            tx.signatures.clear();
+        }
+        assert!(!stripped.verify(&zero));
+        assert!(stripped.verify_with_committed_sig_roots(&zero, &roots));
+        assert!(entries.verify_with_committed_sig_roots(&zero, &roots));
+
+        let mut bad_roots = roots.clone();
+        bad_roots[3] = Some(Hash::new_unique());
+        assert!(!stripped.verify_with_committed_sig_roots(&zero, &bad_roots));
+        // Without its root the stripped entry can't verify
+        let mut bad_roots = roots.clone();
+        bad_roots[2] = None;
+        assert!(!stripped.verify_with_committed_sig_roots(&zero, &bad_roots));
+        assert!(!stripped.verify_with_committed_sig_roots(&zero, &roots[1..]));
     }
 
     #[test]
-    #[should_panic]
-    fn test_next_entry_panic() {
+    fn test_create_ticks_with_failure_at() {
         let zero = Hash::default();
-        let keypair = Keypair::new();
-        let tx = system_transaction::transfer(&keypair, &keypair.pubkey(), 0, zero);
-        next_entry(&zero, 0, vec![tx]);
+        for fail_index in [0, 3, 7] {
+            let ticks = create_ticks_with_failure_at(8, 4, zero, fail_index);
+            assert_eq!(ticks.len(), 8);
+            assert!(!ticks.verify(&zero));
+            assert_eq!(ticks.verifying_prefix_len(&zero), fail_index);
+            assert_eq!(
+                ticks.verify_failing_entries(&zero),
+                vec![&ticks[fail_index]]
+            );
+        }
     }
 
     #[test]
-    fn test_verify_slice1() {
-        solana_logger::setup();
+    fn test_verify_with_max_concurrency() {
         let zero = Hash::default();
-        let one = hash(zero.as_ref());
-        assert!(vec![][..].verify(&zero)); // base case
-        assert!(vec![Entry::new_tick(0, &zero)][..].verify(&zero)); // singleton case 1
-        assert!(!vec![Entry::new_tick(0, &zero)][..].verify(&one)); // singleton case 2, bad
-        assert!(vec![next_entry(&zero, 0, vec![]); 2][..].verify(&zero)); // inductive step
+        let mut entries = create_ticks(64, 4, zero);
+        let last_hash = entries.last().unwrap().hash;
+        entries.push(next_entry(&last_hash, 2, vec![test_tx()]));
+        for max_threads in [0, 1, 2] {
+            assert!(entries.verify_with_max_concurrency(&zero, max_threads));
+            assert!(!entries.verify_with_max_concurrency(&Hash::new_unique(), max_threads));
+        }
 
-        let mut bad_ticks = vec![next_entry(&zero, 0, vec![]); 2];
-        bad_ticks[1].hash = one;
-        assert!(!bad_ticks.verify(&zero)); // inductive step, bad
+        entries[40].num_hashes += 1;
+        for max_threads in [1, 2] {
+            assert!(!entries.verify_with_max_concurrency(&zero, max_threads));
+        }
+        assert!(entries[..0].verify_with_max_concurrency(&zero, 1));
+
+        // Pools are reused, and verification runs on them rather than on the shared pool
+        let pool = capped_thread_pool(2);
+        assert!(Arc::ptr_eq(&pool, &capped_thread_pool(2)));
+        assert_eq!(
+            pool.install(|| install_verify_pool(rayon::current_num_threads)),
+            2
+        );
     }
 
     #[test]
-    fn test_verify_slice_with_hashes1() {
-        solana_logger::setup();
+    fn test_all_transactions_are_votes() {
+        use solana_sdk::vote::{instruction as vote_instruction, state::Vote};
+
         let zero = Hash::default();
-        let one = hash(zero.as_ref());
-        let two = hash(one.as_ref());
-        assert!(vec![][..].verify(&one)); // base case
-        assert!(vec![Entry::new_tick(1, &two)][..].verify(&one)); // singleton case 1
-        assert!(!vec![Entry::new_tick(1, &two)][..].verify(&two)); // singleton case 2, bad
+        let keypair = Keypair::new();
+        let vote_ix = vote_instruction::vote(
+            &Pubkey::new_unique(),
+            &keypair.pubkey(),
+            Vote::new(vec![1], zero),
+        );
+        let vote_tx = Transaction::new_signed_with_payer(
+            &[vote_ix],
+            Some(&keypair.pubkey()),
+            &[&keypair],
+            zero,
+        );
 
-        let mut ticks = vec![next_entry(&one, 1, vec![])];
-        ticks.push(next_entry(&ticks.last().unwrap().hash, 1, vec![]));
-        assert!(ticks.verify(&one)); // inductive step
+        let mut entries = create_ticks(2, 4, zero);
+        assert!(entries.all_transactions_are_votes());
+        assert!(!entries.has_non_vote_transactions());
 
-        let mut bad_ticks = vec![next_entry(&one, 1, vec![])];
-        bad_ticks.push(next_entry(&bad_ticks.last().unwrap().hash, 1, vec![]));
-        bad_ticks[1].hash = one;
-        assert!(!bad_ticks.verify(&one)); // inductive step, bad
+        entries.push(next_entry(&zero, 1, vec![vote_tx.clone(), vote_tx.clone()]));
+        assert!(entries.all_transactions_are_votes());
+        assert!(!entries.has_non_vote_transactions());
+
+        entries.push(next_entry(&zero, 1, vec![vote_tx, test_tx()]));
+        assert!(!entries.all_transactions_are_votes());
+        assert!(entries.has_non_vote_transactions());
     }
 
     #[test]
-    fn test_verify_slice_with_hashes_and_transactions() {
-        solana_logger::setup();
+    fn test_verify_serialized() {
         let zero = Hash::default();
-        let one = hash(zero.as_ref());
-        let two = hash(one.as_ref());
-        let alice_keypair = Keypair::new();
-        let bob_keypair = Keypair::new();
-        let tx0 = system_transaction::transfer(&alice_keypair, &bob_keypair.pubkey(), 1, one);
-        let tx1 = system_transaction::transfer(&bob_keypair, &alice_keypair.pubkey(), 1, one);
-        assert!(vec![][..].verify(&one)); // base case
-        assert!(vec![next_entry(&one, 1, vec![tx0.clone()])][..].verify(&one)); // singleton case 1
-        assert!(!vec![next_entry(&one, 1, vec![tx0.clone()])][..].verify(&two)); // singleton case 2, bad
+        let mut entries = create_ticks(3, 4, zero);
+        let last_hash = entries.last().unwrap().hash;
+        entries.push(next_entry(&last_hash, 2, vec![test_tx(), test_tx()]));
+        let last_hash = entries.last().unwrap().hash;
+        entries.extend(create_ticks(2, 4, last_hash));
+        let bytes = bincode::serialize(&entries).unwrap();
+        assert_eq!(verify_serialized(&bytes, zero), Ok(()));
+        assert_eq!(verify_serialized(&bytes, Hash::new_unique()), Err(8));
+
+        // Offset of entry 4 is the length prefix plus the size of the entries before it
+        let offset = 8 + entries[..4]
+            .iter()
+            .map(|entry| bincode::serialized_size(entry).unwrap())
+            .sum::<u64>();
+        entries[4].hash = Hash::new_unique();
+        let bytes = bincode::serialize(&entries).unwrap();
+        assert_eq!(verify_serialized(&bytes, zero), Err(offset));
+        assert_eq!(
+            bincode::deserialize::<Entry>(&bytes[offset as usize..]).unwrap(),
+            entries[4]
+        );
 
-        let mut ticks = vec![next_entry(&one, 1, vec![tx0.clone()])];
-        ticks.push(next_entry(
-            &ticks.last().unwrap().hash,
+        // Truncated buffers fail at the entry that is cut off, or at the length prefix
+        assert_eq!(
+            verify_serialized(&bytes[..offset as usize + 1], zero),
+            Err(offset)
+        );
+        assert_eq!(verify_serialized(&bytes[..4], zero), Err(0));
+    }
+
+    #[test]
+    fn test_total_entries_verified() {
+        let zero = Hash::default();
+        let ticks = create_ticks(10, 4, zero);
+        let entries = vec![next_entry(&zero, 1, vec![test_tx()])];
+        let before = total_entries_verified();
+        assert!(ticks
+            .start_verify(&zero, VerifyRecyclers::default())
+            .finish_verify());
+        assert!(entries
+            .start_verify(&zero, VerifyRecyclers::default())
+            .finish_verify());
+        assert!(ticks
+            .verify_into(
+                &zero,
+                &mut PinnedVec::default(),
+                &mut PinnedVec::default(),
+                DEFAULT_MIN_GPU_VERIFY_ENTRIES,
+                DEFAULT_MAX_GPU_VERIFY_ENTRIES
+            )
+            .finish_verify());
+        // Other tests may verify concurrently, so only a lower bound holds
+        assert!(total_entries_verified() >= before + 21);
+    }
+
+    #[test]
+    fn test_verify_recent_blockhashes() {
+        let zero = Hash::default();
+        let keypair = Keypair::new();
+        let blockhash = Hash::new_unique();
+        let unknown_blockhash = Hash::new_unique();
+        let transfer =
+            |blockhash| system_transaction::transfer(&keypair, &Pubkey::new_unique(), 1, blockhash);
+        let valid_blockhashes = HashSet::from([zero, blockhash]);
+
+        let mut entries = create_ticks(2, 4, zero);
+        entries.push(next_entry(
+            &zero,
             1,
-            vec![tx1.clone()],
+            vec![transfer(zero), transfer(blockhash)],
         ));
-        assert!(ticks.verify(&one)); // inductive step
+        entries.push(next_entry(&zero, 1, vec![transfer(blockhash)]));
+        assert_eq!(
+            entries.verify_recent_blockhashes(&valid_blockhashes),
+            Ok(())
+        );
 
-        let mut bad_ticks = vec![next_entry(&one, 1, vec![tx0])];
-        bad_ticks.push(next_entry(&bad_ticks.last().unwrap().hash, 1, vec![tx1]));
-        bad_ticks[1].hash = one;
-        assert!(!bad_ticks.verify(&one)); // inductive step, bad
+        entries.push(next_entry(
+            &zero,
+            1,
+            vec![transfer(blockhash), transfer(unknown_blockhash)],
+        ));
+        assert_eq!(
+            entries.verify_recent_blockhashes(&valid_blockhashes),
+            Err((4, 1))
+        );
+        assert_eq!(
+            entries.verify_recent_blockhashes(&HashSet::new()),
+            Err((2, 0))
+        );
     }
 
     #[test]
-    fn test_verify_tick_hash_count() {
-        let hashes_per_tick = 10;
-        let tx = VersionedTransaction::default();
+    fn test_verify_sampled() {
+        let zero = Hash::default();
+        let mut rng = rand::thread_rng();
+        let mut entries = create_ticks(32, 4, zero);
+        let last_hash = entries.last().unwrap().hash;
+        entries.push(next_entry(&last_hash, 2, vec![test_tx()]));
+        let last_hash = entries.last().unwrap().hash;
+        entries.extend(create_ticks(8, 4, last_hash));
+
+        for sample_rate in [0.0, 0.5, 1.0] {
+            assert!(entries.verify_sampled(&zero, sample_rate, &mut rng));
+        }
 
-        let no_hash_tx_entry = Entry {
-            transactions: vec![tx.clone()],
-            ..Entry::default()
-        };
-        let single_hash_tx_entry = Entry {
-            transactions: vec![tx.clone()],
-            num_hashes: 1,
-            ..Entry::default()
-        };
-        let partial_tx_entry = Entry {
-            num_hashes: hashes_per_tick - 1,
-            transactions: vec![tx.clone()],
-            ..Entry::default()
-        };
-        let full_tx_entry = Entry {
-            num_hashes: hashes_per_tick,
-            transactions: vec![tx.clone()],
-            ..Entry::default()
-        };
-        let max_hash_tx_entry = Entry {
-            transactions: vec![tx],
-            num_hashes: u64::MAX,
-            ..Entry::default()
-        };
+        // At a sample rate of 1.0 every corruption is caught, as with `verify`
+        let mut bad = entries.clone();
+        bad[20].num_hashes += 1;
+        assert!(!bad.verify(&zero));
+        assert!(!bad.verify_sampled(&zero, 1.0, &mut rng));
+        // ...while unsampled entries only get the cheap checks
+        assert!(bad.verify_sampled(&zero, 0.0, &mut rng));
+        assert!(!entries.verify_sampled(&Hash::new_unique(), 1.0, &mut rng));
+
+        // Structural problems are caught without sampling
+        let mut bad = entries.clone();
+        bad[32].num_hashes = 0;
+        assert!(!bad.verify_sampled(&zero, 0.0, &mut rng));
+        let mut bad = entries.clone();
+        bad[5].num_hashes = 0;
+        assert!(!bad.verify_sampled(&zero, 0.0, &mut rng));
+    }
 
-        let no_hash_tick_entry = Entry::new_tick(0, &Hash::default());
-        let single_hash_tick_entry = Entry::new_tick(1, &Hash::default());
-        let partial_tick_entry = Entry::new_tick(hashes_per_tick - 1, &Hash::default());
-        let full_tick_entry = Entry::new_tick(hashes_per_tick, &Hash::default());
-        let max_hash_tick_entry = Entry::new_tick(u64::MAX, &Hash::default());
+    #[test]
+    fn test_last_transaction_entry_index() {
+        let zero = Hash::default();
+        let mut entries = create_ticks(2, 4, zero);
+        assert_eq!(entries.last_transaction_entry_index(), None);
+        assert_eq!(entries[..0].last_transaction_entry_index(), None);
+
+        entries.push(next_entry(&zero, 1, vec![test_tx()]));
+        entries.extend(create_ticks(1, 4, zero));
+        entries.push(next_entry(&zero, 1, vec![test_tx(), test_tx()]));
+        assert_eq!(entries.last_transaction_entry_index(), Some(4));
+        entries.extend(create_ticks(3, 4, zero));
+        assert_eq!(entries.last_transaction_entry_index(), Some(4));
+    }
 
-        // empty batch should succeed if hashes_per_tick hasn't been reached
-        let mut tick_hash_count = 0;
-        let mut entries = vec![];
-        assert!(entries.verify_tick_hash_count(&mut tick_hash_count, hashes_per_tick));
-        assert_eq!(tick_hash_count, 0);
+    #[test]
+    fn test_next_hash_known_answer() {
+        // SHA-256 iterated 1000 times over 32 zero bytes, computed independently. sha2 picks
+        // its backend at runtime, SHA-NI where available, so this pins whichever one this host
+        // runs against a reference value.
+        let expected = Hash::new_from_array([
+            54, 193, 203, 79, 130, 106, 228, 44, 235, 168, 72, 34, 126, 12, 95, 120, 97, 120, 202,
+            157, 206, 202, 103, 114, 229, 215, 40, 208, 156, 48, 162, 246,
+        ]);
+        let zero = Hash::default();
+        assert_eq!(next_hash(&zero, 1000, &[]), expected);
+        let ticks = create_ticks(10, 100, zero);
+        assert_eq!(ticks.last().unwrap().hash, expected);
+        assert!(ticks.verify_cpu_generic(&zero).finish_verify());
+    }
 
-        // empty batch should fail if hashes_per_tick has been reached
-        tick_hash_count = hashes_per_tick;
-        assert!(!entries.verify_tick_hash_count(&mut tick_hash_count, hashes_per_tick));
-        assert_eq!(tick_hash_count, hashes_per_tick);
-        tick_hash_count = 0;
+    #[test]
+    fn test_verify_with_steps() {
+        let zero = Hash::default();
+        let mut entries = vec![Entry::new_tick(0, &zero)];
+        entries.extend(create_ticks(2, 4, zero));
+        let last_hash = entries.last().unwrap().hash;
+        let txs = vec![test_tx(), test_tx()];
+        entries.push(next_entry(&last_hash, 3, txs));
+        let mixin = hash_transactions(&entries[3].transactions);
+        entries.extend(create_ticks(1, 4, entries[3].hash));
+
+        let expected_steps = vec![
+            PohStep::Tick { num_hashes: 0 },
+            PohStep::Tick { num_hashes: 4 },
+            PohStep::Tick { num_hashes: 4 },
+            PohStep::Record {
+                num_hashes: 3,
+                mixin,
+            },
+            PohStep::Tick { num_hashes: 4 },
+        ];
+        assert_eq!(
+            entries.verify_with_steps(&zero),
+            (true, expected_steps.clone())
+        );
 
-        // validation is disabled when hashes_per_tick == 0
-        entries = vec![max_hash_tx_entry.clone()];
-        assert!(entries.verify_tick_hash_count(&mut tick_hash_count, 0));
-        assert_eq!(tick_hash_count, 0);
+        entries[3].num_hashes = 2;
+        let (verified, steps) = entries.verify_with_steps(&zero);
+        assert!(!verified);
+        assert!(matches!(steps[3], PohStep::Record { num_hashes: 2, .. }));
+        assert_eq!(steps.len(), expected_steps.len());
+    }
 
-        // partial tick should fail
-        entries = vec![partial_tick_entry.clone()];
-        assert!(!entries.verify_tick_hash_count(&mut tick_hash_count, hashes_per_tick));
-        assert_eq!(tick_hash_count, hashes_per_tick - 1);
-        tick_hash_count = 0;
+    #[test]
+    fn test_verify_signatures_batch() {
+        let zero = Hash::default();
+        let ticks = create_ticks(2, 4, zero);
+        let good = vec![
+            next_entry(&zero, 1, vec![test_tx(), test_tx()]),
+            next_entry(&zero, 1, vec![test_tx()]),
+        ];
+        let bad = vec![
+            next_entry(&zero, 1, vec![test_tx()]),
+            next_entry(&zero, 1, vec![test_tx(), test_invalid_tx()]),
+        ];
+        // Enough transactions to span more than one packet batch
+        let large: Vec<_> = repeat_with(|| next_entry(&zero, 1, vec![test_tx(); 8]))
+            .take(PACKETS_PER_BATCH / 4)
+            .collect();
+        let slices: Vec<&[Entry]> = vec![&good, &ticks, &bad, &large, &good[..0]];
 
-        // full tick entry should succeed
-        entries = vec![no_hash_tx_entry, full_tick_entry.clone()];
-        assert!(entries.verify_tick_hash_count(&mut tick_hash_count, hashes_per_tick));
-        assert_eq!(tick_hash_count, 0);
+        let cpu_verified: Vec<bool> = slices
+            .iter()
+            .map(|entries| {
+                entries
+                    .iter()
+                    .flat_map(|entry| &entry.transactions)
+                    .all(|tx| tx.verify_with_results().into_iter().all(|ok| ok))
+            })
+            .collect();
+        assert_eq!(cpu_verified, vec![true, true, false, true, true]);
+        for secp256k1_program_enabled in [false, true] {
+            for ed25519_program_enabled in [false, true] {
+                assert_eq!(
+                    verify_signatures_batch(
+                        &slices,
+                        secp256k1_program_enabled,
+                        ed25519_program_enabled,
+                        &VerifyRecyclers::default()
+                    ),
+                    cpu_verified
+                );
+            }
+        }
+        assert!(verify_signatures_batch(&[], true, true, &VerifyRecyclers::default()).is_empty());
+    }
 
-        // oversized tick entry should fail
-        assert!(!entries.verify_tick_hash_count(&mut tick_hash_count, hashes_per_tick - 1));
-        assert_eq!(tick_hash_count, hashes_per_tick);
-        tick_hash_count = 0;
+    #[test]
+    fn test_verify_signatures_batch_precompiles() {
+        use solana_sdk::ed25519_instruction::new_ed25519_instruction;
 
-        // partial tx entry without tick entry should succeed
-        entries = vec![partial_tx_entry];
-        assert!(entries.verify_tick_hash_count(&mut tick_hash_count, hashes_per_tick));
-        assert_eq!(tick_hash_count, hashes_per_tick - 1);
-        tick_hash_count = 0;
+        let zero = Hash::default();
+        let payer = Keypair::new();
+        let privkey = ed25519_dalek::Keypair::generate(&mut thread_rng());
+        let mut invalid_instruction = new_ed25519_instruction(&privkey, b"hello");
+        *invalid_instruction.data.last_mut().unwrap() ^= 0xff;
+        // Signed correctly, so only the precompile check fails it
+        let invalid_tx = Transaction::new_signed_with_payer(
+            &[invalid_instruction],
+            Some(&payer.pubkey()),
+            &[&payer],
+            zero,
+        );
+        let good = vec![next_entry(&zero, 1, vec![test_tx()])];
+        let bad = vec![next_entry(&zero, 1, vec![test_tx(), invalid_tx])];
+        let slices: Vec<&[Entry]> = vec![&good, &bad];
+
+        // Only the ed25519 flag gates the invalid ed25519 instruction
+        let verify_recyclers = VerifyRecyclers::default();
+        for secp256k1_program_enabled in [false, true] {
+            assert_eq!(
+                verify_signatures_batch(
+                    &slices,
+                    secp256k1_program_enabled,
+                    false,
+                    &verify_recyclers
+                ),
+                vec![true, true]
+            );
+            assert_eq!(
+                verify_signatures_batch(
+                    &slices,
+                    secp256k1_program_enabled,
+                    true,
+                    &verify_recyclers
+                ),
+                vec![true, false]
+            );
+        }
+    }
 
-        // full tx entry with tick entry should succeed
-        entries = vec![full_tx_entry.clone(), no_hash_tick_entry];
-        assert!(entries.verify_tick_hash_count(&mut tick_hash_count, hashes_per_tick));
-        assert_eq!(tick_hash_count, 0);
+    #[test]
+    fn test_verify_with_exceptions() {
+        let zero = Hash::default();
+        let mut entries = create_ticks(8, 4, zero);
+        let last_hash = entries.last().unwrap().hash;
+        entries.push(next_entry(&last_hash, 2, vec![test_tx()]));
+        assert!(entries.verify_with_exceptions(&zero, &HashSet::new()));
+
+        // The entry after the corrupted one still chains from its recorded hash
+        entries[3].hash = Hash::new_unique();
+        assert!(!entries.verify(&zero));
+        assert!(!entries.verify_with_exceptions(&zero, &HashSet::new()));
+        assert!(entries.verify_with_exceptions(&zero, &HashSet::from([3])));
+        assert!(!entries.verify_with_exceptions(&zero, &HashSet::from([4])));
+
+        entries[8].num_hashes += 1;
+        assert!(!entries.verify_with_exceptions(&zero, &HashSet::from([3])));
+        assert!(entries.verify_with_exceptions(&zero, &HashSet::from([3, 8])));
+    }
 
-        // full tx entry with oversized tick entry should fail
-        entries = vec![full_tx_entry.clone(), single_hash_tick_entry.clone()];
-        assert!(!entries.verify_tick_hash_count(&mut tick_hash_count, hashes_per_tick));
-        assert_eq!(tick_hash_count, hashes_per_tick + 1);
-        tick_hash_count = 0;
+    #[test]
+    fn test_entry_verification_state_tick_count() {
+        let zero = Hash::default();
+        let mut entries = create_ticks(3, 4, zero);
+        let last_hash = entries.last().unwrap().hash;
+        entries.push(next_entry(&last_hash, 2, vec![test_tx()]));
+        let last_hash = entries.last().unwrap().hash;
+        entries.extend(create_ticks(2, 4, last_hash));
+        assert_eq!(entries.tick_count(), 5);
+
+        // Both the CPU and GPU (if available) paths
+        for min_gpu_verify_entries in [0, usize::MAX] {
+            let recyclers =
+                VerifyRecyclers::default().with_min_gpu_verify_entries(min_gpu_verify_entries);
+            let mut state = entries.start_verify(&zero, recyclers);
+            assert_eq!(state.tick_count(), entries.tick_count());
+            assert!(state.finish_verify());
+        }
+        // The CPU paths count ticks in the pass that verifies them
+        assert_eq!(entries.verify_cpu_generic(&zero).tick_count(), 5);
+        let (has_avx2, _) = x86_simd_features();
+        if api().is_some() && has_avx2 {
+            assert_eq!(entries.verify_cpu_x86_simd(&zero, 8).tick_count(), 5);
+        }
+        let state = entries[..0].start_verify(&zero, VerifyRecyclers::default());
+        assert_eq!(state.tick_count(), 0);
+    }
 
-        // full tx entry without tick entry should fail
-        entries = vec![full_tx_entry];
-        assert!(!entries.verify_tick_hash_count(&mut tick_hash_count, hashes_per_tick));
-        assert_eq!(tick_hash_count, hashes_per_tick);
-        tick_hash_count = 0;
+    #[test]
+    fn test_verify_transaction_account_uniqueness() {
+        let zero = Hash::default();
+        let mut entries = create_ticks(2, 4, zero);
+        entries.push(next_entry(&zero, 1, vec![test_tx(), test_tx()]));
+        entries.push(next_entry(&zero, 1, vec![test_tx()]));
+        assert_eq!(entries.verify_transaction_account_uniqueness(), Ok(()));
+
+        let mut malformed = test_tx();
+        let duplicate_key = malformed.message.account_keys[0];
+        malformed.message.account_keys.push(duplicate_key);
+        entries[3]
+            .transactions
+            .push(VersionedTransaction::from(malformed));
+        assert_eq!(entries.verify_transaction_account_uniqueness(), Err((3, 1)));
+    }
 
-        // tx entry and a tick should succeed
-        entries = vec![single_hash_tx_entry.clone(), partial_tick_entry];
-        assert!(entries.verify_tick_hash_count(&mut tick_hash_count, hashes_per_tick));
-        assert_eq!(tick_hash_count, 0);
+    #[test]
+    fn test_verifying_suffix_len() {
+        let zero = Hash::default();
+        let mut entries = create_ticks(6, 4, zero);
+        let last_hash = entries.last().unwrap().hash;
+        entries.push(next_entry(&last_hash, 2, vec![test_tx()]));
+        entries.extend(create_ticks(3, 4, entries.last().unwrap().hash));
+        let end_hash = entries.last().unwrap().hash;
+        assert_eq!(entries.verifying_suffix_len(&end_hash), 10);
+        assert_eq!(entries.verifying_suffix_len(&Hash::new_unique()), 0);
+        assert_eq!(entries[..0].verifying_suffix_len(&zero), 0);
+
+        // Entry 3 no longer verifies, but its recorded hash still anchors the tail
+        let mut bad = entries.clone();
+        bad[3].num_hashes += 1;
+        assert_eq!(bad.verifying_suffix_len(&end_hash), 7);
+
+        // Entry 4 no longer links to the corrupted hash of entry 3
+        let mut bad = entries.clone();
+        bad[3].hash = Hash::new_unique();
+        assert_eq!(bad.verifying_suffix_len(&end_hash), 6);
+        assert_eq!(bad.verifying_prefix_len(&zero), 3);
+    }
 
-        // many tx entries and a tick should succeed
-        let tx_entries: Vec<Entry> = (0..hashes_per_tick - 1)
-            .map(|_| single_hash_tx_entry.clone())
-            .collect();
-        entries = [tx_entries, vec![single_hash_tick_entry]].concat();
-        assert!(entries.verify_tick_hash_count(&mut tick_hash_count, hashes_per_tick));
-        assert_eq!(tick_hash_count, 0);
+    #[test]
+    fn test_verify_marking_precompiles() {
+        use solana_sdk::ed25519_instruction::new_ed25519_instruction;
 
-        // check overflow saturation should fail
-        entries = vec![full_tick_entry.clone(), max_hash_tick_entry];
-        assert!(!entries.verify_tick_hash_count(&mut tick_hash_count, hashes_per_tick));
-        assert_eq!(tick_hash_count, u64::MAX);
-        tick_hash_count = 0;
+        let zero = Hash::default();
+        let payer = Keypair::new();
+        let privkey = ed25519_dalek::Keypair::generate(&mut thread_rng());
+        let instruction = new_ed25519_instruction(&privkey, b"hello");
+        let precompile_tx = |instruction| {
+            Transaction::new_signed_with_payer(
+                &[instruction],
+                Some(&payer.pubkey()),
+                &[&payer],
+                zero,
+            )
+        };
 
-        // check overflow saturation should fail
-        entries = vec![max_hash_tx_entry, full_tick_entry];
-        assert!(!entries.verify_tick_hash_count(&mut tick_hash_count, hashes_per_tick));
-        assert_eq!(tick_hash_count, u64::MAX);
+        let mut entries = create_ticks(2, 4, zero);
+        entries.push(next_entry(&zero, 1, vec![test_tx(), test_tx()]));
+        entries.push(next_entry(
+            &zero,
+            1,
+            vec![test_tx(), precompile_tx(instruction.clone())],
+        ));
+        assert_eq!(
+            entries.verify_marking_precompiles(true),
+            (true, vec![(3, 1)])
+        );
+
+        let mut invalid_instruction = instruction;
+        *invalid_instruction.data.last_mut().unwrap() ^= 0xff;
+        entries.push(next_entry(
+            &zero,
+            1,
+            vec![precompile_tx(invalid_instruction)],
+        ));
+        assert_eq!(
+            entries.verify_marking_precompiles(true),
+            (false, vec![(3, 1), (4, 0)])
+        );
+        // Still marked, but not verified, while the program is disabled
+        assert_eq!(
+            entries.verify_marking_precompiles(false),
+            (true, vec![(3, 1), (4, 0)])
+        );
     }
 
     #[test]
-    fn test_poh_verify_fuzz() {
-        solana_logger::setup();
-        for _ in 0..100 {
-            let mut time = Measure::start("ticks");
-            let num_ticks = thread_rng().gen_range(1, 100);
-            info!("create {} ticks:", num_ticks);
-            let mut entries = create_random_ticks(num_ticks, 100, Hash::default());
-            time.stop();
-
-            let mut modified = false;
-            if thread_rng().gen_ratio(1, 2) {
-                modified = true;
-                let modify_idx = thread_rng().gen_range(0, num_ticks) as usize;
-                entries[modify_idx].hash = hash(&[1, 2, 3]);
-            }
-
-            info!("done.. {}", time);
-            let mut time = Measure::start("poh");
-            let res = entries.verify(&Hash::default());
-            assert_eq!(res, !modified);
-            time.stop();
-            info!("{} {}", time, res);
-        }
+    fn test_transaction_order_commitment() {
+        let zero = Hash::default();
+        let txs: Vec<_> = repeat_with(test_tx).take(4).collect();
+        let mut entries = create_ticks(1, 4, zero);
+        entries.push(next_entry(&zero, 1, txs[..2].to_vec()));
+        entries.push(next_entry(&zero, 1, txs[2..].to_vec()));
+        let commitment = entries.transaction_order_commitment();
+        assert_eq!(entries.transaction_order_commitment(), commitment);
+
+        // Only the global order matters, not the entry boundaries or ticks
+        let regrouped = vec![
+            next_entry(&zero, 1, txs[..1].to_vec()),
+            next_entry(&zero, 1, txs[1..].to_vec()),
+        ];
+        assert_eq!(regrouped.transaction_order_commitment(), commitment);
+
+        // Swapping transactions across entries changes it
+        let mut reordered = entries.clone();
+        reordered[1].transactions[1] = entries[2].transactions[0].clone();
+        reordered[2].transactions[0] = entries[1].transactions[1].clone();
+        assert_ne!(reordered.transaction_order_commitment(), commitment);
+        assert_ne!(entries[..1].transaction_order_commitment(), commitment);
     }
 }
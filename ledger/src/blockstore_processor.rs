@@ -1217,9 +1217,12 @@ fn confirm_slot_entries(
     };
 
     let transaction_verification_start = Instant::now();
+    // `Bank::verify_transaction` already verifies precompiles against the bank's feature set
     let transaction_verification_result = entry::start_verify_transactions(
         entries,
         skip_verification,
+        false,
+        false,
         recyclers.clone(),
         Arc::new(verify_transaction),
     );